@@ -0,0 +1,231 @@
+/*!
+Congruence closure over terms, for reasoning about equality with uninterpreted function symbols
+(e.g. from `a = b` and `b = c` concluding `f(a, x) = f(c, x)`).
+
+This Expr model has no dedicated function-application variant -- predicates/applications like
+`f(a, x)` are flat `Expr::Var` strings (see `EqualitySubstitution` in `rules.rs`). `parse_application`
+is the minimal parsing needed to see the application structure through that encoding; everything
+else here is the textbook congruence closure algorithm: a union-find over every subterm, plus a
+use list per class so that merging two classes can cheaply find applications that may now agree.
+*/
+
+use crate::expr::Expr;
+
+use std::collections::HashMap;
+
+/// See whether `e` is a flat `Var` string of the shape `head(arg1, arg2, ...)`. Returns the head
+/// symbol and parsed argument subterms, or `None` if `e` isn't of this shape (a plain
+/// constant/variable, or any non-`Var` expression) -- such terms are still given a node by
+/// `intern`, just an opaque one with no signature.
+pub(crate) fn parse_application(e: &Expr) -> Option<(String, Vec<Expr>)> {
+    let Expr::Var { name } = e else { return None };
+    let name = name.trim();
+    let open = name.find('(')?;
+    if open == 0 || !name.ends_with(')') {
+        return None;
+    }
+    let head = name[..open].to_string();
+    let inner = &name[open + 1..name.len() - 1];
+    if inner.trim().is_empty() {
+        return Some((head, vec![]));
+    }
+    let args = split_top_level_commas(inner)
+        .into_iter()
+        .map(|a| Expr::var(a.trim()))
+        .collect();
+    Some((head, args))
+}
+
+/// Split `s` on commas that aren't nested inside parentheses, e.g. `"a, f(b, c)"` splits into
+/// `["a", " f(b, c)"]` rather than four pieces.
+fn split_top_level_commas(s: &str) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut depth = 0i32;
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&s[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&s[start..]);
+    parts
+}
+
+/// A union-find over every subterm reachable from the interned equalities, with a use list per
+/// class so that `union` can propagate congruence (equal arguments imply equal applications) to
+/// a fixpoint instead of re-scanning every pair of applications after every merge.
+struct CongruenceClosure {
+    parent: Vec<usize>,
+    rank: Vec<usize>,
+    /// `signature[i]` is `Some((head, arg_ids))` if node `i` is a function application, with
+    /// `arg_ids` as originally interned (not normalized to current representatives -- that
+    /// normalization has to happen fresh in `congruence_key`, since it changes after every
+    /// union).
+    signature: Vec<Option<(String, Vec<usize>)>>,
+    /// `use_list[r]`, for a class representative `r`, lists every application node that has (a
+    /// node in) `r`'s class as one of its arguments.
+    use_list: Vec<Vec<usize>>,
+    ids: HashMap<Expr, usize>,
+}
+
+impl CongruenceClosure {
+    fn new() -> Self {
+        CongruenceClosure {
+            parent: vec![],
+            rank: vec![],
+            signature: vec![],
+            use_list: vec![],
+            ids: HashMap::new(),
+        }
+    }
+
+    fn fresh_node(&mut self) -> usize {
+        let id = self.parent.len();
+        self.parent.push(id);
+        self.rank.push(0);
+        self.signature.push(None);
+        self.use_list.push(vec![]);
+        id
+    }
+
+    fn find(&mut self, i: usize) -> usize {
+        if self.parent[i] != i {
+            let root = self.find(self.parent[i]);
+            self.parent[i] = root;
+        }
+        self.parent[i]
+    }
+
+    /// Register `e`, and recursively its arguments if it parses as a function application, as
+    /// congruence-closure nodes. Idempotent: re-interning an already-seen `Expr` returns its
+    /// existing id.
+    fn intern(&mut self, e: &Expr) -> usize {
+        if let Some(&id) = self.ids.get(e) {
+            return id;
+        }
+        let id = self.fresh_node();
+        self.ids.insert(e.clone(), id);
+        if let Some((head, args)) = parse_application(e) {
+            let arg_ids: Vec<usize> = args.iter().map(|a| self.intern(a)).collect();
+            for &a in &arg_ids {
+                let r = self.find(a);
+                self.use_list[r].push(id);
+            }
+            self.signature[id] = Some((head, arg_ids));
+        }
+        id
+    }
+
+    /// The signature of application node `i`, with its argument ids normalized to their current
+    /// representatives. `None` if `i` isn't a function application.
+    fn congruence_key(&mut self, i: usize) -> Option<(String, Vec<usize>)> {
+        let (head, args) = self.signature[i].clone()?;
+        Some((head, args.into_iter().map(|a| self.find(a)).collect()))
+    }
+
+    /// Merge the classes of `a` and `b`, then propagate congruence to a fixpoint: whenever two
+    /// classes merge, every pair of applications drawn from either one's use list is re-checked
+    /// against the (now current) representatives of its arguments, and merged too if they agree.
+    fn union(&mut self, a: usize, b: usize) {
+        let mut worklist = vec![(a, b)];
+        while let Some((a, b)) = worklist.pop() {
+            let ra = self.find(a);
+            let rb = self.find(b);
+            if ra == rb {
+                continue;
+            }
+            let (small, big) = if self.rank[ra] < self.rank[rb] {
+                (ra, rb)
+            } else {
+                (rb, ra)
+            };
+            self.parent[small] = big;
+            if self.rank[small] == self.rank[big] {
+                self.rank[big] += 1;
+            }
+            let mut combined = std::mem::take(&mut self.use_list[big]);
+            combined.extend(std::mem::take(&mut self.use_list[small]));
+            for i in 0..combined.len() {
+                for j in (i + 1)..combined.len() {
+                    let (p, q) = (combined[i], combined[j]);
+                    if self.find(p) == self.find(q) {
+                        continue;
+                    }
+                    if let (Some(sp), Some(sq)) = (self.congruence_key(p), self.congruence_key(q)) {
+                        if sp == sq {
+                            worklist.push((p, q));
+                        }
+                    }
+                }
+            }
+            self.use_list[big] = combined;
+        }
+    }
+
+    fn congruent(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+}
+
+/// Does the equality `goal` (`(s, t)` for `s = t`) follow from the equalities in `premises` by
+/// congruence closure? Reflexivity, symmetry and transitivity fall out of the union-find for
+/// free; the interesting case is e.g. `a = b`, `b = c` |- `f(a, x) = f(c, x)`, which additionally
+/// needs the "equal arguments imply equal applications" congruence rule implemented by
+/// `CongruenceClosure::union`.
+pub fn entails(premises: &[(Expr, Expr)], goal: &(Expr, Expr)) -> bool {
+    let mut cc = CongruenceClosure::new();
+    let premise_ids: Vec<(usize, usize)> = premises
+        .iter()
+        .map(|(l, r)| (cc.intern(l), cc.intern(r)))
+        .collect();
+    let (gl, gr) = (cc.intern(&goal.0), cc.intern(&goal.1));
+    for (l, r) in premise_ids {
+        cc.union(l, r);
+    }
+    cc.congruent(gl, gr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entails_transitivity() {
+        let premises = [(Expr::var("a"), Expr::var("b")), (Expr::var("b"), Expr::var("c"))];
+        let goal = (Expr::var("a"), Expr::var("c"));
+        assert!(entails(&premises, &goal));
+    }
+
+    #[test]
+    fn test_entails_congruence_over_application() {
+        // a = b, b = c |- f(a, x) = f(c, x)
+        let premises = [(Expr::var("a"), Expr::var("b")), (Expr::var("b"), Expr::var("c"))];
+        let goal = (Expr::var("f(a, x)"), Expr::var("f(c, x)"));
+        assert!(entails(&premises, &goal));
+    }
+
+    #[test]
+    fn test_entails_rejects_unrelated_equality() {
+        let premises = [(Expr::var("a"), Expr::var("b"))];
+        let goal = (Expr::var("f(a, x)"), Expr::var("f(c, x)"));
+        assert!(!entails(&premises, &goal));
+    }
+
+    #[test]
+    fn test_parse_application_nested() {
+        assert_eq!(
+            parse_application(&Expr::var("f(g(a), x)")),
+            Some((
+                "f".to_string(),
+                vec![Expr::var("g(a)"), Expr::var("x")]
+            ))
+        );
+        assert_eq!(parse_application(&Expr::var("a")), None);
+    }
+}
@@ -0,0 +1,202 @@
+/*!
+Semantic counterexample generation for failed rule checks.
+
+When a propositional rule's `check` rejects a step, the user only gets a textual
+`ProofCheckError`. Inspired by Isabelle's `quickcheck`, this enumerates truth assignments over
+the atoms appearing in the dependencies and the conclusion, looking for one that satisfies
+every dependency while falsifying the conclusion -- turning a bare red-X into "your step is
+invalid under P=true, Q=false".
+
+`find_countermodel` extends this past the propositional fragment to quantified steps, by
+grounding quantifiers out over increasing finite domain sizes (1..=max_domain) and falling back
+to the same brute-force propositional search on the result.
+*/
+
+use crate::expr::Expr;
+use crate::expr::Op;
+use crate::expr::QuantKind;
+
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+
+/// Maximum number of distinct atoms to enumerate all assignments for; beyond this the search
+/// bails out rather than taking exponential time.
+pub const MAX_ATOMS: usize = 20;
+
+/// Evaluate the boolean fragment of `Expr` (`And`/`Or`/`Not`/`Implies`/`Bicon`/`Equiv`,
+/// `Contra`) under `assignment`. Returns `None` if `expr` mentions an atom missing from
+/// `assignment`, or uses a construct outside the boolean fragment (quantifiers, application).
+pub fn eval(expr: &Expr, assignment: &BTreeMap<String, bool>) -> Option<bool> {
+    match expr {
+        Expr::Var { name } => assignment.get(name).copied(),
+        Expr::Contra => Some(false),
+        Expr::Not { operand } => eval(operand, assignment).map(|b| !b),
+        Expr::Impl { left, right } => {
+            let l = eval(left, assignment)?;
+            let r = eval(right, assignment)?;
+            Some(!l || r)
+        }
+        Expr::Assoc { op, exprs } => {
+            let vals = exprs
+                .iter()
+                .map(|e| eval(e, assignment))
+                .collect::<Option<Vec<_>>>()?;
+            match op {
+                Op::And => Some(vals.iter().all(|b| *b)),
+                Op::Or => Some(vals.iter().any(|b| *b)),
+                Op::Bicon | Op::Equiv => Some(vals.windows(2).all(|w| w[0] == w[1])),
+            }
+        }
+        _ => None,
+    }
+}
+
+/// True iff `expr` is built entirely from the boolean fragment `eval` understands.
+fn is_propositional(expr: &Expr) -> bool {
+    match expr {
+        Expr::Var { .. } | Expr::Contra => true,
+        Expr::Not { operand } => is_propositional(operand),
+        Expr::Impl { left, right } => is_propositional(left) && is_propositional(right),
+        Expr::Assoc { exprs, .. } => exprs.iter().all(is_propositional),
+        _ => false,
+    }
+}
+
+/// Collect every distinct atomic variable occurring in `expr`.
+fn atoms(expr: &Expr, out: &mut HashSet<String>) {
+    match expr {
+        Expr::Var { name } => {
+            out.insert(name.clone());
+        }
+        Expr::Not { operand } => atoms(operand, out),
+        Expr::Impl { left, right } => {
+            atoms(left, out);
+            atoms(right, out);
+        }
+        Expr::Assoc { exprs, .. } => {
+            for e in exprs {
+                atoms(e, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Search for a truth assignment over the atoms of `deps` and `conclusion` that makes every
+/// dependency true while making `conclusion` false -- i.e. a counterexample to `deps ⊨
+/// conclusion`. Returns `None` if the step isn't purely propositional, if there are more than
+/// `MAX_ATOMS` distinct atoms to search over, or if no counterexample exists (meaning the
+/// rejection wasn't due to a genuine semantic failure in the propositional fragment).
+pub fn find_counterexample(deps: &[Expr], conclusion: &Expr) -> Option<BTreeMap<String, bool>> {
+    if !deps.iter().all(is_propositional) || !is_propositional(conclusion) {
+        return None;
+    }
+
+    let mut atom_set = HashSet::new();
+    for dep in deps {
+        atoms(dep, &mut atom_set);
+    }
+    atoms(conclusion, &mut atom_set);
+    let mut atom_list: Vec<String> = atom_set.into_iter().collect();
+    atom_list.sort();
+    if atom_list.len() > MAX_ATOMS {
+        return None;
+    }
+
+    let n = atom_list.len();
+    for bits in 0u32..(1u32 << n) {
+        let assignment: BTreeMap<String, bool> = atom_list
+            .iter()
+            .enumerate()
+            .map(|(i, name)| (name.clone(), (bits >> i) & 1 == 1))
+            .collect();
+        let deps_hold = deps.iter().all(|d| eval(d, &assignment) == Some(true));
+        let conclusion_fails = eval(conclusion, &assignment) == Some(false);
+        if deps_hold && conclusion_fails {
+            return Some(assignment);
+        }
+    }
+    None
+}
+
+/// A falsifying model found by `find_countermodel`: a finite domain size (`None` for a purely
+/// propositional model, where there's no domain to speak of) plus a truth assignment to the
+/// ground atoms that witnesses it.
+pub struct Model {
+    pub domain_size: Option<usize>,
+    pub assignment: BTreeMap<String, bool>,
+}
+
+impl std::fmt::Display for Model {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if let Some(n) = self.domain_size {
+            writeln!(f, "domain = {{1..{}}}", n)?;
+        }
+        let rendered = self
+            .assignment
+            .iter()
+            .map(|(name, val)| format!("{} = {}", name, val))
+            .collect::<Vec<_>>()
+            .join(", ");
+        write!(f, "{}", rendered)
+    }
+}
+
+/// Like `find_counterexample`, but also handles quantified `premises`/`conclusion` by grounding
+/// every quantifier out over domain sizes `1..=max_domain`, smallest first, and running the
+/// propositional search on the resulting (still possibly quantifier-free) ground formulas.
+/// Domain elements are synthetic constants (`#1`, `#2`, ...); this doesn't model a "real"
+/// first-order structure, just whether some domain of that size admits a falsifying
+/// interpretation of the predicates/constants mentioned.
+pub fn find_countermodel(premises: &[Expr], conclusion: &Expr, max_domain: usize) -> Option<Model> {
+    if premises.iter().all(is_propositional) && is_propositional(conclusion) {
+        return find_counterexample(premises, conclusion).map(|assignment| Model {
+            domain_size: None,
+            assignment,
+        });
+    }
+    for domain_size in 1..=max_domain.max(1) {
+        let ground_premises: Vec<Expr> = premises.iter().map(|e| ground_out(e, domain_size)).collect();
+        let ground_conclusion = ground_out(conclusion, domain_size);
+        if let Some(assignment) = find_counterexample(&ground_premises, &ground_conclusion) {
+            return Some(Model {
+                domain_size: Some(domain_size),
+                assignment,
+            });
+        }
+    }
+    None
+}
+
+/// Replace every quantifier in `expr` with a finite conjunction (`Forall`) or disjunction
+/// (`Exists`) of its body over the domain elements `#1..=#domain_size`, recursing into the
+/// result so that nested quantifiers are ground out too.
+fn ground_out(expr: &Expr, domain_size: usize) -> Expr {
+    match expr {
+        Expr::Var { .. } | Expr::Contra => expr.clone(),
+        Expr::Not { operand } => Expr::Not {
+            operand: Box::new(ground_out(operand, domain_size)),
+        },
+        Expr::Impl { left, right } => Expr::Impl {
+            left: Box::new(ground_out(left, domain_size)),
+            right: Box::new(ground_out(right, domain_size)),
+        },
+        Expr::Assoc { op, exprs } => Expr::Assoc {
+            op: op.clone(),
+            exprs: exprs.iter().map(|e| ground_out(e, domain_size)).collect(),
+        },
+        Expr::Quant { kind, name, body } => {
+            let op = match kind {
+                QuantKind::Forall => Op::And,
+                QuantKind::Exists => Op::Or,
+            };
+            let instances = (1..=domain_size)
+                .map(|i| {
+                    let elem = Expr::var(format!("#{}", i));
+                    ground_out(&crate::expr::subst(*body.clone(), name, elem), domain_size)
+                })
+                .collect();
+            Expr::Assoc { op, exprs: instances }
+        }
+    }
+}
@@ -54,7 +54,10 @@ Adding the tests and implementing the rule can be interleaved; it's convenient t
     - if default metadata doesn't apply to all rules of the type, add an empty match block (e.g. `PrepositionalInference`)
 */
 
+use crate::congruence;
+use crate::counterexample;
 use crate::equivs;
+use crate::sat::TseitinEncoder;
 use crate::expr::Equal;
 use crate::expr::Expr;
 use crate::expr::Op;
@@ -62,6 +65,7 @@ use crate::expr::QuantKind;
 use crate::proofs::PJRef;
 use crate::proofs::Proof;
 use crate::rewrite_rules::RewriteRule;
+use crate::tableau;
 
 use std::collections::BTreeSet;
 use std::collections::HashMap;
@@ -104,6 +108,11 @@ pub enum PredicateInference {
     ForallElim,
     ExistsIntro,
     ExistsElim,
+    EqualitySubstitution,
+    CongruenceClosure,
+    UniqueExistsIntro,
+    UniqueExistsElim,
+    EqualityElimination,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -121,6 +130,8 @@ pub enum BooleanEquivalence {
     Absorption,
     Reduction,
     Adjacency,
+    ConjunctiveNormalForm,
+    DisjunctiveNormalForm,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -153,6 +164,8 @@ pub enum AutomationRelatedRules {
     AsymmetricTautology,
     Resolution,
     TautologicalConsequence,
+    Prover,
+    Tautology,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -164,6 +177,7 @@ pub enum QuantifierEquivalence {
     AristoteleanSquare,
     QuantifierDistribution,
     PrenexLaws,
+    OnePointRule,
 }
 
 /// This should be the default rule when creating a new step in a UI. It
@@ -258,6 +272,11 @@ pub mod RuleM {
         [ForallElim, "UNIVERSAL_INSTANTIATION", (SharedChecks(Inr(Inl(PredicateInference::ForallElim))))],
         [ExistsIntro, "EXISTENTIAL_GENERALIZATION", (SharedChecks(Inr(Inl(PredicateInference::ExistsIntro))))],
         [ExistsElim, "EXISTENTIAL_INSTANTIATION", (SharedChecks(Inr(Inl(PredicateInference::ExistsElim))))],
+        [EqualitySubstitution, "EQUALITY_SUBSTITUTION", (SharedChecks(Inr(Inl(PredicateInference::EqualitySubstitution))))],
+        [CongruenceClosure, "CONGRUENCE_CLOSURE", (SharedChecks(Inr(Inl(PredicateInference::CongruenceClosure))))],
+        [UniqueExistsIntro, "UNIQUE_EXISTENTIAL_GENERALIZATION", (SharedChecks(Inr(Inl(PredicateInference::UniqueExistsIntro))))],
+        [UniqueExistsElim, "UNIQUE_EXISTENTIAL_INSTANTIATION", (SharedChecks(Inr(Inl(PredicateInference::UniqueExistsElim))))],
+        [EqualityElimination, "EQUALITY_ELIMINATION", (SharedChecks(Inr(Inl(PredicateInference::EqualityElimination))))],
 
         [ModusTollens, "MODUS_TOLLENS", (SharedChecks(Inr(Inr(Inr(Inr(Inl(RedundantPrepositionalInference::ModusTollens)))))))],
         [HypotheticalSyllogism, "HYPOTHETICAL_SYLLOGISM", (SharedChecks(Inr(Inr(Inr(Inr(Inl(RedundantPrepositionalInference::HypotheticalSyllogism)))))))],
@@ -277,6 +296,8 @@ pub mod RuleM {
         [Absorption, "ABSORPTION", (SharedChecks(Inr(Inr(Inl(BooleanEquivalence::Absorption)))))],
         [Reduction, "REDUCTION", (SharedChecks(Inr(Inr(Inl(BooleanEquivalence::Reduction)))))],
         [Adjacency, "ADJACENCY", (SharedChecks(Inr(Inr(Inl(BooleanEquivalence::Adjacency)))))],
+        [ConjunctiveNormalForm, "CONJUNCTIVE_NORMAL_FORM", (SharedChecks(Inr(Inr(Inl(BooleanEquivalence::ConjunctiveNormalForm)))))],
+        [DisjunctiveNormalForm, "DISJUNCTIVE_NORMAL_FORM", (SharedChecks(Inr(Inr(Inl(BooleanEquivalence::DisjunctiveNormalForm)))))],
 
         [CondComplement, "CONDITIONAL_COMPLEMENT", (SharedChecks(Inr(Inr(Inr(Inl(ConditionalEquivalence::Complement))))))],
         [CondIdentity, "CONDITIONAL_IDENTITY", (SharedChecks(Inr(Inr(Inr(Inl(ConditionalEquivalence::Identity))))))],
@@ -295,6 +316,8 @@ pub mod RuleM {
         [AsymmetricTautology, "ASYMMETRIC_TAUTOLOGY", (SharedChecks(Inr(Inr(Inr(Inr(Inr(Inl(AutomationRelatedRules::AsymmetricTautology))))))))],
         [Resolution, "RESOLUTION", (SharedChecks(Inr(Inr(Inr(Inr(Inr(Inl(AutomationRelatedRules::Resolution))))))))],
         [TautologicalConsequence, "TAUTOLOGICAL_CONSEQUENCE", (SharedChecks(Inr(Inr(Inr(Inr(Inr(Inl(AutomationRelatedRules::TautologicalConsequence))))))))],
+        [Prover, "PROVER", (SharedChecks(Inr(Inr(Inr(Inr(Inr(Inl(AutomationRelatedRules::Prover))))))))],
+        [Tautology, "TAUTOLOGY", (SharedChecks(Inr(Inr(Inr(Inr(Inr(Inl(AutomationRelatedRules::Tautology))))))))],
 
         [QuantifierNegation, "QUANTIFIER_NEGATION", (SharedChecks(Inr(Inr(Inr(Inr(Inr(Inr(Inl(QuantifierEquivalence::QuantifierNegation)))))))))],
         [NullQuantification, "NULL_QUANTIFICATION", (SharedChecks(Inr(Inr(Inr(Inr(Inr(Inr(Inl(QuantifierEquivalence::NullQuantification)))))))))],
@@ -303,6 +326,7 @@ pub mod RuleM {
         [AristoteleanSquare, "ARISTOTELEAN_SQUARE", (SharedChecks(Inr(Inr(Inr(Inr(Inr(Inr(Inl(QuantifierEquivalence::AristoteleanSquare)))))))))],
         [QuantifierDistribution, "QUANTIFIER_DISTRIBUTION", (SharedChecks(Inr(Inr(Inr(Inr(Inr(Inr(Inl(QuantifierEquivalence::QuantifierDistribution)))))))))],
         [PrenexLaws, "PRENEX_LAWS", (SharedChecks(Inr(Inr(Inr(Inr(Inr(Inr(Inl(QuantifierEquivalence::PrenexLaws)))))))))],
+        [OnePointRule, "ONE_POINT_RULE", (SharedChecks(Inr(Inr(Inr(Inr(Inr(Inr(Inl(QuantifierEquivalence::OnePointRule)))))))))],
         [EmptyRule, "EMPTY_RULE", (SharedChecks(Inr(Inr(Inr(Inr(Inr(Inr(Inr(Inl(super::EmptyRule))))))))))]
     }
 }
@@ -320,6 +344,10 @@ pub enum RuleClassification {
     QuantifierEquivalence,
     #[strum(to_string = "Misc. Inference")]
     MiscInference,
+    /// Rules whose soundness depends on the law of excluded middle (or an equivalent, like
+    /// double-negation elimination), so that the GUI can grey them out in `LogicMode::Intuitionistic`.
+    #[strum(to_string = "Classical-Only")]
+    Classical,
 }
 
 impl RuleClassification {
@@ -332,6 +360,22 @@ impl RuleClassification {
     }
 }
 
+/// Whether proof checking allows rules that depend on the law of excluded middle.
+/// Isabelle-style instructors wanting to enforce constructive reasoning (cf. `IFOL`/`iprover`)
+/// can check a proof in `Intuitionistic` mode; `SharedChecks::check` then rejects any rule
+/// classified `RuleClassification::Classical` with `ProofCheckError::NotConstructive`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LogicMode {
+    Classical,
+    Intuitionistic,
+}
+
+impl Default for LogicMode {
+    fn default() -> Self {
+        LogicMode::Classical
+    }
+}
+
 /// aris::rules::RuleT contains metadata and implementations of the rules
 pub trait RuleT {
     /// get_name gets the name of the rule for display in the GUI
@@ -342,14 +386,36 @@ pub trait RuleT {
     fn num_deps(&self) -> Option<usize>;
     /// num_subdeps is used by SharedChecks to ensure that the right number of subproof dependencies are provided, None indicates that no checking is done (e.g. for variadic rules)
     fn num_subdeps(&self) -> Option<usize>;
-    /// check that expr is a valid conclusion of the rule given the corresponding lists of dependencies and subproof dependencies, returning Ok(()) on success, and an error to display in the GUI on failure
+    /// check that expr is a valid conclusion of the rule given the corresponding lists of dependencies and subproof dependencies, returning Ok(()) on success, and an error to display in the GUI on failure.
+    /// `mode` is threaded through so that `SharedChecks` can reject classical-only rules when checking in `LogicMode::Intuitionistic`; individual rules don't need to inspect it themselves.
     fn check<P: Proof>(
         self,
         p: &P,
         expr: Expr,
         deps: Vec<PJRef<P>>,
         sdeps: Vec<P::SubproofReference>,
+        mode: LogicMode,
     ) -> Result<(), ProofCheckError<PJRef<P>, P::SubproofReference>>;
+    /// Like `check`, but on success also returns a `RuleCertificate` witnessing *why* the step
+    /// holds, for rules able to produce one -- letting downstream tooling re-verify the inference
+    /// independently, or the GUI show the concrete derivation instead of a bare pass. Defaults to
+    /// delegating to `check` and reporting no certificate, so the handful of `RuleT` impls that
+    /// can't produce one (i.e. everything except `AutomationRelatedRules`) don't need to know this
+    /// method exists.
+    fn check_with_cert<P: Proof>(
+        self,
+        p: &P,
+        expr: Expr,
+        deps: Vec<PJRef<P>>,
+        sdeps: Vec<P::SubproofReference>,
+        mode: LogicMode,
+    ) -> Result<Option<RuleCertificate>, ProofCheckError<PJRef<P>, P::SubproofReference>>
+    where
+        Self: Sized,
+    {
+        self.check(p, expr, deps, sdeps, mode)?;
+        Ok(None)
+    }
 }
 
 impl<A: RuleT, B: RuleT> RuleT for Coproduct<A, B> {
@@ -383,10 +449,24 @@ impl<A: RuleT, B: RuleT> RuleT for Coproduct<A, B> {
         expr: Expr,
         deps: Vec<PJRef<P>>,
         sdeps: Vec<P::SubproofReference>,
+        mode: LogicMode,
     ) -> Result<(), ProofCheckError<PJRef<P>, P::SubproofReference>> {
         match self {
-            Inl(x) => x.check(p, expr, deps, sdeps),
-            Inr(x) => x.check(p, expr, deps, sdeps),
+            Inl(x) => x.check(p, expr, deps, sdeps, mode),
+            Inr(x) => x.check(p, expr, deps, sdeps, mode),
+        }
+    }
+    fn check_with_cert<P: Proof>(
+        self,
+        p: &P,
+        expr: Expr,
+        deps: Vec<PJRef<P>>,
+        sdeps: Vec<P::SubproofReference>,
+        mode: LogicMode,
+    ) -> Result<Option<RuleCertificate>, ProofCheckError<PJRef<P>, P::SubproofReference>> {
+        match self {
+            Inl(x) => x.check_with_cert(p, expr, deps, sdeps, mode),
+            Inr(x) => x.check_with_cert(p, expr, deps, sdeps, mode),
         }
     }
 }
@@ -409,9 +489,20 @@ impl RuleT for frunk_core::coproduct::CNil {
         _expr: Expr,
         _deps: Vec<PJRef<P>>,
         _sdeps: Vec<P::SubproofReference>,
+        _mode: LogicMode,
     ) -> Result<(), ProofCheckError<PJRef<P>, P::SubproofReference>> {
         match self {}
     }
+    fn check_with_cert<P: Proof>(
+        self,
+        _p: &P,
+        _expr: Expr,
+        _deps: Vec<PJRef<P>>,
+        _sdeps: Vec<P::SubproofReference>,
+        _mode: LogicMode,
+    ) -> Result<Option<RuleCertificate>, ProofCheckError<PJRef<P>, P::SubproofReference>> {
+        match self {}
+    }
 }
 
 impl<T: RuleT> RuleT for SharedChecks<T> {
@@ -433,6 +524,7 @@ impl<T: RuleT> RuleT for SharedChecks<T> {
         expr: Expr,
         deps: Vec<PJRef<P>>,
         sdeps: Vec<P::SubproofReference>,
+        mode: LogicMode,
     ) -> Result<(), ProofCheckError<PJRef<P>, P::SubproofReference>> {
         use ProofCheckError::*;
         if let Some(directs) = self.num_deps() {
@@ -445,8 +537,66 @@ impl<T: RuleT> RuleT for SharedChecks<T> {
                 return Err(IncorrectSubDepCount(sdeps, subs));
             }
         }
+        if mode == LogicMode::Intuitionistic
+            && self.get_classifications().contains(&RuleClassification::Classical)
+        {
+            return Err(NotConstructive(self.get_name()));
+        }
         // TODO: enforce that each subproof has exactly 1 premise
-        self.0.check(p, expr, deps, sdeps)
+        let dep_exprs = deps
+            .iter()
+            .filter_map(|d| p.lookup_expr(d))
+            .collect::<Vec<Expr>>();
+        let conclusion = expr.clone();
+        match self.0.check(p, expr, deps, sdeps, mode) {
+            Err(Other(msg)) => {
+                // For purely propositional rejections, try to turn the bare error message into
+                // an actionable counterexample: a truth assignment satisfying every dependency
+                // while falsifying the conclusion.
+                match counterexample::find_counterexample(&dep_exprs, &conclusion) {
+                    Some(assignment) => Err(FalsifiedByAssignment(assignment, msg)),
+                    None => Err(Other(msg)),
+                }
+            }
+            result => result,
+        }
+    }
+    fn check_with_cert<P: Proof>(
+        self,
+        p: &P,
+        expr: Expr,
+        deps: Vec<PJRef<P>>,
+        sdeps: Vec<P::SubproofReference>,
+        mode: LogicMode,
+    ) -> Result<Option<RuleCertificate>, ProofCheckError<PJRef<P>, P::SubproofReference>> {
+        use ProofCheckError::*;
+        if let Some(directs) = self.num_deps() {
+            if deps.len() != directs {
+                return Err(IncorrectDepCount(deps, directs));
+            }
+        }
+        if let Some(subs) = self.num_subdeps() {
+            if sdeps.len() != subs {
+                return Err(IncorrectSubDepCount(sdeps, subs));
+            }
+        }
+        if mode == LogicMode::Intuitionistic
+            && self.get_classifications().contains(&RuleClassification::Classical)
+        {
+            return Err(NotConstructive(self.get_name()));
+        }
+        let dep_exprs = deps
+            .iter()
+            .filter_map(|d| p.lookup_expr(d))
+            .collect::<Vec<Expr>>();
+        let conclusion = expr.clone();
+        match self.0.check_with_cert(p, expr, deps, sdeps, mode) {
+            Err(Other(msg)) => match counterexample::find_counterexample(&dep_exprs, &conclusion) {
+                Some(assignment) => Err(FalsifiedByAssignment(assignment, msg)),
+                None => Err(Other(msg)),
+            },
+            result => result,
+        }
     }
 }
 
@@ -513,15 +663,21 @@ impl RuleT for PrepositionalInference {
                 ret.insert(Elimination);
             }
         }
+        // NotElim is double-negation elimination (¬¬A |- A), which is only sound classically.
+        if let NotElim = self {
+            ret.insert(Classical);
+        }
         ret
     }
     fn num_deps(&self) -> Option<usize> {
         use PrepositionalInference::*;
         match self {
             Reit | AndElim | OrIntro | OrElim | NotElim | ContradictionElim => Some(1),
-            ContradictionIntro | ImpElim | BiconditionalElim | EquivalenceElim => Some(2),
+            ImpElim | BiconditionalElim | EquivalenceElim => Some(2),
             NotIntro | ImpIntro => Some(0),
-            AndIntro | BiconditionalIntro | EquivalenceIntro => None, // AndIntro can have arbitrarily many conjuncts in one application
+            // AndIntro can have arbitrarily many conjuncts in one application; ContradictionIntro
+            // likewise takes any number of mutually-inconsistent premises (see its `check` arm).
+            AndIntro | BiconditionalIntro | EquivalenceIntro | ContradictionIntro => None,
         }
     }
     fn num_subdeps(&self) -> Option<usize> {
@@ -539,6 +695,7 @@ impl RuleT for PrepositionalInference {
         conclusion: Expr,
         deps: Vec<PJRef<P>>,
         sdeps: Vec<P::SubproofReference>,
+        _mode: LogicMode,
     ) -> Result<(), ProofCheckError<PJRef<P>, P::SubproofReference>> {
         use PrepositionalInference::*;
         use ProofCheckError::*;
@@ -770,9 +927,50 @@ impl RuleT for PrepositionalInference {
             }
             ContradictionIntro => {
                 if let Expr::Contra = conclusion {
-                    let prem1 = p.lookup_expr_or_die(&deps[0])?;
-                    let prem2 = p.lookup_expr_or_die(&deps[1])?;
-                    do_expressions_contradict::<P>(&prem1, &prem2)
+                    let prems = deps
+                        .iter()
+                        .map(|d| p.lookup_expr_or_die(d))
+                        .collect::<Result<Vec<Expr>, _>>()?;
+                    // Fast path: the common case of exactly two directly-contradictory premises
+                    // (`a`, `¬a`) doesn't need a SAT call.
+                    if let [prem1, prem2] = &prems[..] {
+                        if do_expressions_contradict::<P>(prem1, prem2).is_ok() {
+                            return Ok(());
+                        }
+                    }
+                    // General case: `prems` are mutually inconsistent iff their conjunction is
+                    // unsatisfiable, decided by Tseitin-encoding the premises and running DPLL
+                    // (`sat::find_model`). A satisfying assignment is worth keeping: it's a
+                    // concrete counter-model showing the premises can all hold, which is far more
+                    // useful than an opaque rejection.
+                    let mut encoder = TseitinEncoder::new();
+                    let vars: Vec<i64> = prems.iter().map(|e| encoder.encode(e)).collect();
+                    for v in vars {
+                        encoder.assert_literal(v);
+                    }
+                    let descriptions = encoder.describe_vars();
+                    let clauses = encoder.into_clauses();
+                    match crate::sat::find_model(&clauses) {
+                        None => Ok(()),
+                        Some(model) => {
+                            let mut assignment: Vec<(String, bool)> = model
+                                .into_iter()
+                                .filter_map(|lit| {
+                                    descriptions.get(&lit.abs()).map(|name| (name.clone(), lit > 0))
+                                })
+                                .collect();
+                            assignment.sort();
+                            let pretty = assignment
+                                .iter()
+                                .map(|(name, val)| format!("{} = {}", name, val))
+                                .collect::<Vec<_>>()
+                                .join(", ");
+                            Err(Other(format!(
+                                "The premises are satisfiable, not mutually contradictory: {}",
+                                pretty
+                            )))
+                        }
+                    }
                 } else {
                     Err(ConclusionOfWrongForm(Expr::Contra))
                 }
@@ -984,6 +1182,11 @@ impl RuleT for PredicateInference {
             ForallElim => "∀ Elimination",
             ExistsIntro => "∃ Introduction",
             ExistsElim => "∃ Elimination",
+            EqualitySubstitution => "= Substitution",
+            CongruenceClosure => "Congruence Closure",
+            UniqueExistsIntro => "∃! Introduction",
+            UniqueExistsElim => "∃! Elimination",
+            EqualityElimination => "= Elimination",
         }
         .into()
     }
@@ -992,8 +1195,9 @@ impl RuleT for PredicateInference {
         use RuleClassification::*;
         let mut ret = HashSet::new();
         match self {
-            ForallIntro | ExistsIntro => ret.insert(Introduction),
-            ForallElim | ExistsElim => ret.insert(Elimination),
+            ForallIntro | ExistsIntro | UniqueExistsIntro => ret.insert(Introduction),
+            ForallElim | ExistsElim | UniqueExistsElim => ret.insert(Elimination),
+            EqualitySubstitution | CongruenceClosure | EqualityElimination => ret.insert(MiscInference),
         };
         ret
     }
@@ -1002,6 +1206,9 @@ impl RuleT for PredicateInference {
         match self {
             ExistsIntro | ExistsElim | ForallElim => Some(1),
             ForallIntro => Some(0),
+            EqualitySubstitution | UniqueExistsIntro | EqualityElimination => Some(2),
+            CongruenceClosure => None, // an arbitrary number of equality premises
+            UniqueExistsElim => None, // either 1 dep (drop uniqueness) or 2 (derive an equality)
         }
     }
     fn num_subdeps(&self) -> Option<usize> {
@@ -1009,6 +1216,7 @@ impl RuleT for PredicateInference {
         match self {
             ExistsIntro | ForallElim => Some(0),
             ForallIntro | ExistsElim => Some(1),
+            EqualitySubstitution | CongruenceClosure | UniqueExistsIntro | UniqueExistsElim | EqualityElimination => Some(0),
         }
     }
     fn check<P: Proof>(
@@ -1017,6 +1225,7 @@ impl RuleT for PredicateInference {
         conclusion: Expr,
         deps: Vec<PJRef<P>>,
         sdeps: Vec<P::SubproofReference>,
+        _mode: LogicMode,
     ) -> Result<(), ProofCheckError<PJRef<P>, P::SubproofReference>> {
         use PredicateInference::*;
         use ProofCheckError::*;
@@ -1244,7 +1453,334 @@ impl RuleT for PredicateInference {
                     conclusion
                 )))
             }
+            EqualitySubstitution => {
+                // s = t, phi
+                // -----------
+                // phi[s := t]  (or phi[t := s]), replacing one or more free occurrences
+                // without capturing a variable bound by a quantifier in phi.
+                let equation = p.lookup_expr_or_die(&deps[0])?;
+                let phi = p.lookup_expr_or_die(&deps[1])?;
+                let (s, t) = match &equation {
+                    Expr::Assoc { op: Op::Equiv, exprs } if exprs.len() == 2 => {
+                        (exprs[0].clone(), exprs[1].clone())
+                    }
+                    _ => {
+                        return Err(DepOfWrongForm(
+                            equation,
+                            Expr::assocplaceholder(Op::Equiv),
+                        ))
+                    }
+                };
+                let bound = HashSet::new();
+                for (from, to) in [(&s, &t), (&t, &s)] {
+                    let mut replaced_something = false;
+                    if eq_subst_matches(&phi, &conclusion, from, to, &bound, &mut replaced_something)
+                        && replaced_something
+                    {
+                        return Ok(());
+                    }
+                }
+                Err(Other(format!(
+                    "{} is not obtainable from {} by substituting free occurrences of one side of {} with the other, without capturing a variable bound in {}.",
+                    conclusion, phi, equation, phi
+                )))
+            }
+            CongruenceClosure => {
+                let as_equality = |e: &Expr| match e {
+                    Expr::Assoc { op: Op::Equiv, exprs } if exprs.len() == 2 => {
+                        Some((exprs[0].clone(), exprs[1].clone()))
+                    }
+                    _ => None,
+                };
+                let premises = deps
+                    .iter()
+                    .map(|d| p.lookup_expr_or_die(d))
+                    .collect::<Result<Vec<Expr>, _>>()?
+                    .into_iter()
+                    .map(|e| {
+                        as_equality(&e).ok_or_else(|| {
+                            DepOfWrongForm(e.clone(), Expr::assocplaceholder(Op::Equiv))
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                let goal = as_equality(&conclusion)
+                    .ok_or_else(|| ConclusionOfWrongForm(Expr::assocplaceholder(Op::Equiv)))?;
+                if congruence::entails(&premises, &goal) {
+                    Ok(())
+                } else {
+                    let premises_str = premises
+                        .iter()
+                        .map(|(l, r)| format!("{} = {}", l, r))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    Err(Other(format!(
+                        "{} is not derivable from {{{}}} by congruence closure (reflexivity, symmetry, transitivity, and equal arguments implying equal function applications).",
+                        conclusion, premises_str
+                    )))
+                }
+            }
+            UniqueExistsIntro => {
+                // This Expr model has no dedicated unique-existence quantifier kind (that would
+                // live on `QuantKind` alongside `Forall`/`Exists`, with its own pretty-printer and
+                // parser support), so ∃!x φ(x) is checked in its standard expansion
+                // ∃x(φ(x) ∧ ∀y(φ(y) → y=x)): one dependency witnesses φ for some term, the other
+                // proves that witness is unique.
+                if let Expr::Quant {
+                    kind: QuantKind::Exists,
+                    ref name,
+                    ref body,
+                } = conclusion
+                {
+                    if let Expr::Assoc { op: Op::And, exprs } = &**body {
+                        if let [phi_x, uniqueness] = &exprs[..] {
+                            if let Expr::Quant {
+                                kind: QuantKind::Forall,
+                                name: y,
+                                body: uniq_body,
+                            } = uniqueness
+                            {
+                                if let Expr::Impl { left, right } = &**uniq_body {
+                                    let phi_y = crate::expr::subst(phi_x.clone(), name, Expr::var(y));
+                                    let eq_ok = matches!(
+                                        &**right,
+                                        Expr::Assoc { op: Op::Equiv, exprs } if exprs.len() == 2
+                                            && ((exprs[0] == Expr::var(y) && exprs[1] == Expr::var(name))
+                                                || (exprs[1] == Expr::var(y) && exprs[0] == Expr::var(name)))
+                                    );
+                                    if phi_y != **left || !eq_ok {
+                                        return Err(Other(format!(
+                                            "{} is not the expected uniqueness clause ∀{}({} → {}={}).",
+                                            uniqueness, y, phi_y, y, name
+                                        )));
+                                    }
+                                    let prem1 = p.lookup_expr_or_die(&deps[0])?;
+                                    let prem2 = p.lookup_expr_or_die(&deps[1])?;
+                                    return either_order(
+                                        &prem1,
+                                        &prem2,
+                                        |witness, uniq_dep| {
+                                            let t = match unifies_wrt_var::<P>(phi_x, witness, name) {
+                                                Ok(t) => t,
+                                                Err(_) => return AnyOrderResult::WrongOrder,
+                                            };
+                                            let expected_uniq = Expr::Quant {
+                                                kind: QuantKind::Forall,
+                                                name: y.clone(),
+                                                body: Box::new(Expr::Impl {
+                                                    left: Box::new(phi_y.clone()),
+                                                    right: Box::new(Expr::Assoc {
+                                                        op: Op::Equiv,
+                                                        exprs: vec![Expr::var(y), t],
+                                                    }),
+                                                }),
+                                            };
+                                            if uniq_dep == &expected_uniq {
+                                                AnyOrderResult::Ok
+                                            } else {
+                                                AnyOrderResult::WrongOrder
+                                            }
+                                        },
+                                        || {
+                                            Other(format!(
+                                                "Expected one dependency witnessing {} for some term, and one dependency proving its uniqueness.",
+                                                phi_x
+                                            ))
+                                        },
+                                    );
+                                }
+                            }
+                        }
+                    }
+                    Err(Other(format!(
+                        "{} is not of the expected unique-existence form ∃x(φ(x) ∧ ∀y(φ(y) → y=x)).",
+                        conclusion
+                    )))
+                } else {
+                    Err(ConclusionOfWrongForm(Expr::quant_placeholder(
+                        QuantKind::Exists,
+                    )))
+                }
+            }
+            UniqueExistsElim => {
+                // From ∃!x φ(x), either ∃x φ(x) follows directly (dropping the uniqueness
+                // conjunct), or, given a second dependency φ(a) ∧ φ(b), a=b follows from the
+                // uniqueness conjunct.
+                let prem = p.lookup_expr_or_die(&deps[0])?;
+                if let Expr::Quant {
+                    kind: QuantKind::Exists,
+                    ref name,
+                    ref body,
+                } = prem
+                {
+                    if let Expr::Assoc { op: Op::And, exprs } = &**body {
+                        if let [phi_x, _uniqueness] = &exprs[..] {
+                            return match deps.len() {
+                                1 => {
+                                    let expected = Expr::Quant {
+                                        kind: QuantKind::Exists,
+                                        name: name.clone(),
+                                        body: Box::new(phi_x.clone()),
+                                    };
+                                    if conclusion == expected {
+                                        Ok(())
+                                    } else {
+                                        Err(Other(format!(
+                                            "{} doesn't drop the uniqueness conjunct of {} to get {}.",
+                                            conclusion, prem, expected
+                                        )))
+                                    }
+                                }
+                                2 => {
+                                    let both = p.lookup_expr_or_die(&deps[1])?;
+                                    if let Expr::Assoc { op: Op::And, exprs: both_exprs } = &both {
+                                        if let [wit_a, wit_b] = &both_exprs[..] {
+                                            let a = unifies_wrt_var::<P>(phi_x, wit_a, name)?;
+                                            let b = unifies_wrt_var::<P>(phi_x, wit_b, name)?;
+                                            let matches_conclusion = matches!(
+                                                &conclusion,
+                                                Expr::Assoc { op: Op::Equiv, exprs } if exprs.len() == 2
+                                                    && ((exprs[0] == a && exprs[1] == b) || (exprs[0] == b && exprs[1] == a))
+                                            );
+                                            if matches_conclusion {
+                                                Ok(())
+                                            } else {
+                                                Err(Other(format!(
+                                                    "{} is not {}={} as derived from {} and {}.",
+                                                    conclusion, a, b, prem, both
+                                                )))
+                                            }
+                                        } else {
+                                            Err(DepOfWrongForm(both, Expr::assocplaceholder(Op::And)))
+                                        }
+                                    } else {
+                                        Err(DepOfWrongForm(both, Expr::assocplaceholder(Op::And)))
+                                    }
+                                }
+                                n => Err(Other(format!(
+                                    "UniqueExistsElim takes 1 or 2 dependencies, got {}.",
+                                    n
+                                ))),
+                            };
+                        }
+                    }
+                    Err(Other(format!(
+                        "{} is not of the expected unique-existence form ∃x(φ(x) ∧ ∀y(φ(y) → y=x)).",
+                        prem
+                    )))
+                } else {
+                    Err(DepOfWrongForm(prem, Expr::quant_placeholder(QuantKind::Exists)))
+                }
+            }
+            EqualityElimination => {
+                // Leibniz's law, a=b, P(a) |- P(b): like EqualitySubstitution, but the equality
+                // and the formula to rewrite may be given as dependencies in either order.
+                let dep1 = p.lookup_expr_or_die(&deps[0])?;
+                let dep2 = p.lookup_expr_or_die(&deps[1])?;
+                either_order(
+                    &dep1,
+                    &dep2,
+                    |equation, phi| {
+                        let (s, t) = match equation {
+                            Expr::Assoc { op: Op::Equiv, exprs } if exprs.len() == 2 => {
+                                (exprs[0].clone(), exprs[1].clone())
+                            }
+                            _ => return AnyOrderResult::WrongOrder,
+                        };
+                        let bound = HashSet::new();
+                        for (from, to) in [(&s, &t), (&t, &s)] {
+                            let mut replaced_something = false;
+                            if eq_subst_matches(phi, &conclusion, from, to, &bound, &mut replaced_something)
+                                && replaced_something
+                            {
+                                return AnyOrderResult::Ok;
+                            }
+                        }
+                        AnyOrderResult::Err(Other(format!(
+                            "{} is not obtainable from {} by substituting free occurrences of one side of {} with the other, without capturing a variable bound in {}.",
+                            conclusion, phi, equation, phi
+                        )))
+                    },
+                    || {
+                        Other("Expected one dependency of the form a=b and one dependency to rewrite using it.".to_string())
+                    },
+                )
+            }
+        }
+    }
+}
+
+/// Walk `phi` and `phi2` in lockstep, checking that `phi2` is `phi` with one or more free
+/// occurrences of `from` replaced by `to` (and otherwise unchanged), setting `*replaced` if at
+/// least one such replacement was found. A replacement is rejected if it would capture (or
+/// escape into) a variable bound by an enclosing quantifier in `phi`, tracked via `bound`.
+/// Flat application strings like `P(a)` are compared head and argument by argument (via
+/// `congruence::parse_application`), so a term inside a predicate can be rewritten too.
+fn eq_subst_matches(
+    phi: &Expr,
+    phi2: &Expr,
+    from: &Expr,
+    to: &Expr,
+    bound: &HashSet<String>,
+    replaced: &mut bool,
+) -> bool {
+    if phi == phi2 {
+        return true;
+    }
+    if phi == from && phi2 == to {
+        let captures = crate::expr::free_vars(from)
+            .iter()
+            .chain(crate::expr::free_vars(to).iter())
+            .any(|v| bound.contains(v));
+        if captures {
+            return false;
+        }
+        *replaced = true;
+        return true;
+    }
+    match (phi, phi2) {
+        (Expr::Not { operand: a }, Expr::Not { operand: b }) => {
+            eq_subst_matches(a, b, from, to, bound, replaced)
+        }
+        (
+            Expr::Impl { left: al, right: ar },
+            Expr::Impl { left: bl, right: br },
+        ) => {
+            eq_subst_matches(al, bl, from, to, bound, replaced)
+                && eq_subst_matches(ar, br, from, to, bound, replaced)
+        }
+        (Expr::Assoc { op: ao, exprs: ae }, Expr::Assoc { op: bo, exprs: be }) => {
+            ao == bo
+                && ae.len() == be.len()
+                && ae
+                    .iter()
+                    .zip(be.iter())
+                    .all(|(a, b)| eq_subst_matches(a, b, from, to, bound, replaced))
+        }
+        (
+            Expr::Quant { kind: ak, name: an, body: ab },
+            Expr::Quant { kind: bk, name: bn, body: bb },
+        ) => {
+            if ak != bk || an != bn {
+                return false;
+            }
+            let mut bound = bound.clone();
+            bound.insert(an.clone());
+            eq_subst_matches(ab, bb, from, to, &bound, replaced)
+        }
+        (Expr::Var { .. }, Expr::Var { .. }) => {
+            match (congruence::parse_application(phi), congruence::parse_application(phi2)) {
+                (Some((ha, aa)), Some((hb, ab))) => {
+                    ha == hb
+                        && aa.len() == ab.len()
+                        && aa
+                            .iter()
+                            .zip(ab.iter())
+                            .all(|(a, b)| eq_subst_matches(a, b, from, to, bound, replaced))
+                }
+                _ => false,
+            }
         }
+        _ => false,
     }
 }
 
@@ -1314,6 +1850,218 @@ fn check_by_rewrite_rule_non_confl<P: Proof>(
     }
 }
 
+/// Eliminate `→` and `Bicon`/`Equiv` in favor of `∧`/`∨`/`¬`, the first stage of `to_cnf`/`to_dnf`.
+/// `a → b` becomes `¬a ∨ b`; an n-ary equivalence chain `a1 <-> ... <-> an` -- true iff every
+/// operand agrees, matching `counterexample::eval`'s windows-pairwise semantics -- becomes
+/// `(a1∧...∧an) ∨ (¬a1∧...∧¬an)`.
+fn eliminate_conditionals(e: Expr) -> Expr {
+    match e {
+        Expr::Var { .. } | Expr::Contra => e,
+        Expr::Not { operand } => Expr::Not {
+            operand: Box::new(eliminate_conditionals(*operand)),
+        },
+        Expr::Impl { left, right } => Expr::Assoc {
+            op: Op::Or,
+            exprs: vec![
+                Expr::Not { operand: Box::new(eliminate_conditionals(*left)) },
+                eliminate_conditionals(*right),
+            ],
+        },
+        Expr::Assoc { op: Op::Bicon | Op::Equiv, exprs } => {
+            let exprs: Vec<Expr> = exprs.into_iter().map(eliminate_conditionals).collect();
+            Expr::Assoc {
+                op: Op::Or,
+                exprs: vec![
+                    Expr::Assoc { op: Op::And, exprs: exprs.clone() },
+                    Expr::Assoc {
+                        op: Op::And,
+                        exprs: exprs
+                            .into_iter()
+                            .map(|e| Expr::Not { operand: Box::new(e) })
+                            .collect(),
+                    },
+                ],
+            }
+        }
+        Expr::Assoc { op, exprs } => Expr::Assoc {
+            op,
+            exprs: exprs.into_iter().map(eliminate_conditionals).collect(),
+        },
+        Expr::Quant { kind, name, body } => Expr::Quant {
+            kind,
+            name,
+            body: Box::new(eliminate_conditionals(*body)),
+        },
+    }
+}
+
+/// Push negations down to the leaves, given an input already free of `→`/`Bicon`/`Equiv` (see
+/// `eliminate_conditionals`): De Morgan (`¬(a∧b) ≡ ¬a∨¬b`, dually for `∨`, including the 0-ary
+/// cases `¬⊤ ≡ ⊥` and `¬⊥ ≡ ⊤`), `¬¬a ≡ a`, and `¬⊥(Contra) ≡ ⊤` (the 0-ary `And`). Quantifiers
+/// and atoms are left as opaque leaves.
+fn to_nnf(e: Expr) -> Expr {
+    match e {
+        Expr::Not { operand } => match *operand {
+            Expr::Not { operand } => to_nnf(*operand),
+            Expr::Contra => Expr::Assoc { op: Op::And, exprs: vec![] },
+            Expr::Assoc { op: Op::And, exprs } => Expr::Assoc {
+                op: Op::Or,
+                exprs: exprs
+                    .into_iter()
+                    .map(|e| to_nnf(Expr::Not { operand: Box::new(e) }))
+                    .collect(),
+            },
+            Expr::Assoc { op: Op::Or, exprs } => Expr::Assoc {
+                op: Op::And,
+                exprs: exprs
+                    .into_iter()
+                    .map(|e| to_nnf(Expr::Not { operand: Box::new(e) }))
+                    .collect(),
+            },
+            other => Expr::Not { operand: Box::new(to_nnf(other)) },
+        },
+        Expr::Assoc { op, exprs } => Expr::Assoc {
+            op,
+            exprs: exprs.into_iter().map(to_nnf).collect(),
+        },
+        Expr::Quant { kind, name, body } => Expr::Quant {
+            kind,
+            name,
+            body: Box::new(to_nnf(*body)),
+        },
+        other => other,
+    }
+}
+
+/// Distribute `outer` over `inner` to a fixpoint (CNF: `outer` = `Or`, `inner` = `And`; DNF: the
+/// reverse), given an NNF input. Whenever an `outer`-node has an `inner`-node among its operands,
+/// that `inner`-node's operands are each combined with the rest of the `outer`-node's operands
+/// under a fresh `outer`, all wrapped in the `inner`-node's operator -- exposing the `inner`
+/// operator one level higher, so repeating this bottom-up reaches a fixpoint where every `inner`
+/// is above every `outer`.
+fn distribute(e: Expr, outer: Op, inner: Op) -> Expr {
+    match e {
+        Expr::Assoc { op, exprs } if op == outer => {
+            let exprs: Vec<Expr> = exprs.into_iter().map(|e| distribute(e, outer, inner)).collect();
+            match exprs.iter().position(|e| matches!(e, Expr::Assoc { op, .. } if *op == inner)) {
+                Some(i) => {
+                    let mut exprs = exprs;
+                    let Expr::Assoc { exprs: inner_exprs, .. } = exprs.remove(i) else {
+                        unreachable!()
+                    };
+                    let rest = exprs;
+                    let distributed = Expr::Assoc {
+                        op: inner,
+                        exprs: inner_exprs
+                            .into_iter()
+                            .map(|ie| {
+                                let mut operands = rest.clone();
+                                operands.push(ie);
+                                Expr::Assoc { op: outer, exprs: operands }
+                            })
+                            .collect(),
+                    };
+                    distribute(distributed, outer, inner)
+                }
+                None => Expr::Assoc { op, exprs },
+            }
+        }
+        Expr::Assoc { op, exprs } => Expr::Assoc {
+            op,
+            exprs: exprs.into_iter().map(|e| distribute(e, outer, inner)).collect(),
+        },
+        other => other,
+    }
+}
+
+/// Flatten nested occurrences of the same associative operator (`And(a, And(b, c))` becomes
+/// `And(a, b, c)`), then sort and deduplicate the operands by `Expr`'s total order, so the result
+/// is a canonical representative of its normal form regardless of the original clause order or
+/// duplication.
+fn flatten_sort_dedup(e: Expr) -> Expr {
+    match e {
+        Expr::Assoc { op, exprs } => {
+            let mut flat = vec![];
+            for e in exprs.into_iter().map(flatten_sort_dedup) {
+                match e {
+                    Expr::Assoc { op: inner_op, exprs: inner_exprs } if inner_op == op => {
+                        flat.extend(inner_exprs)
+                    }
+                    other => flat.push(other),
+                }
+            }
+            flat.sort();
+            flat.dedup();
+            Expr::Assoc { op, exprs: flat }
+        }
+        Expr::Not { operand } => Expr::Not { operand: Box::new(flatten_sort_dedup(*operand)) },
+        Expr::Quant { kind, name, body } => Expr::Quant {
+            kind,
+            name,
+            body: Box::new(flatten_sort_dedup(*body)),
+        },
+        other => other,
+    }
+}
+
+/// Normalize `e` to conjunctive normal form: a canonical `And` of `Or`-clauses (degenerating to
+/// a single clause or `⊤`/`⊥` at the extremes), via `eliminate_conditionals`, then `to_nnf`, then
+/// distributing `Or` over `And` to a fixpoint, then `flatten_sort_dedup`.
+fn to_cnf(e: Expr) -> Expr {
+    flatten_sort_dedup(distribute(to_nnf(eliminate_conditionals(e)), Op::Or, Op::And))
+}
+
+/// Normalize `e` to disjunctive normal form: the dual of `to_cnf`, distributing `And` over `Or`.
+fn to_dnf(e: Expr) -> Expr {
+    flatten_sort_dedup(distribute(to_nnf(eliminate_conditionals(e)), Op::And, Op::Or))
+}
+
+/// Shared implementation of `ConjunctiveNormalForm`/`DisjunctiveNormalForm`: accept the step iff
+/// `normalize` maps the premise and the stated conclusion to the same canonical form, and
+/// otherwise report which clauses of the expected normal form are missing from the conclusion,
+/// or present in the conclusion but not expected.
+fn check_by_normal_form<P: Proof>(
+    p: &P,
+    deps: Vec<PJRef<P>>,
+    conclusion: Expr,
+    form_name: &str,
+    normalize: fn(Expr) -> Expr,
+) -> Result<(), ProofCheckError<PJRef<P>, P::SubproofReference>> {
+    let premise = p.lookup_expr_or_die(&deps[0])?;
+    let expected = normalize(premise.clone());
+    let actual = normalize(conclusion.clone());
+    if expected == actual {
+        return Ok(());
+    }
+    let clauses_of = |e: &Expr| -> BTreeSet<Expr> {
+        match e {
+            Expr::Assoc { exprs, .. } => exprs.iter().cloned().collect(),
+            other => std::iter::once(other.clone()).collect(),
+        }
+    };
+    let expected_clauses = clauses_of(&expected);
+    let actual_clauses = clauses_of(&actual);
+    let missing: Vec<String> = expected_clauses
+        .difference(&actual_clauses)
+        .map(ToString::to_string)
+        .collect();
+    let extra: Vec<String> = actual_clauses
+        .difference(&expected_clauses)
+        .map(ToString::to_string)
+        .collect();
+    let mut msg = format!("{} is not the {} of {}", conclusion, form_name, premise);
+    if !missing.is_empty() {
+        msg += &format!(": missing {}", missing.join(", "));
+    }
+    if !missing.is_empty() && !extra.is_empty() {
+        msg += ";";
+    }
+    if !extra.is_empty() {
+        msg += &format!(" extra {}", extra.join(", "));
+    }
+    Err(ProofCheckError::Other(msg))
+}
+
 impl RuleT for BooleanEquivalence {
     fn get_name(&self) -> String {
         use BooleanEquivalence::*;
@@ -1331,14 +2079,30 @@ impl RuleT for BooleanEquivalence {
             Absorption => "Absorption",
             Reduction => "Reduction",
             Adjacency => "Adjacency",
+            ConjunctiveNormalForm => "Conjunctive Normal Form",
+            DisjunctiveNormalForm => "Disjunctive Normal Form",
         }
         .into()
     }
     fn get_classifications(&self) -> HashSet<RuleClassification> {
-        [RuleClassification::BooleanEquivalence]
-            .iter()
-            .cloned()
-            .collect()
+        let mut ret: HashSet<RuleClassification> =
+            [RuleClassification::BooleanEquivalence].iter().cloned().collect();
+        // DoubleNegation encodes ¬¬A ≡ A, which only holds classically; the normal-form rules
+        // push negations inward to NNF using the same double-negation elimination step, so they
+        // inherit the same classical-only restriction. DeMorgan includes ¬(P∧Q) ≡ ¬P∨¬Q, which
+        // holds only in the ¬P∨¬Q -> ¬(P∧Q) direction intuitionistically, and is checked by
+        // normalizing both sides, so it is classified `Classical` for the same reason as
+        // `QuantifierDistribution`.
+        if matches!(
+            self,
+            BooleanEquivalence::DoubleNegation
+                | BooleanEquivalence::DeMorgan
+                | BooleanEquivalence::ConjunctiveNormalForm
+                | BooleanEquivalence::DisjunctiveNormalForm
+        ) {
+            ret.insert(RuleClassification::Classical);
+        }
+        ret
     }
     fn num_deps(&self) -> Option<usize> {
         Some(1)
@@ -1352,6 +2116,7 @@ impl RuleT for BooleanEquivalence {
         conclusion: Expr,
         deps: Vec<PJRef<P>>,
         _sdeps: Vec<P::SubproofReference>,
+        _mode: LogicMode,
     ) -> Result<(), ProofCheckError<PJRef<P>, P::SubproofReference>> {
         use BooleanEquivalence::*;
         match self {
@@ -1393,6 +2158,12 @@ impl RuleT for BooleanEquivalence {
             Adjacency => {
                 check_by_rewrite_rule_confl(p, deps, conclusion, false, &equivs::ADJACENCY)
             }
+            ConjunctiveNormalForm => {
+                check_by_normal_form(p, deps, conclusion, "conjunctive normal form", to_cnf)
+            }
+            DisjunctiveNormalForm => {
+                check_by_normal_form(p, deps, conclusion, "disjunctive normal form", to_dnf)
+            }
         }
     }
 }
@@ -1418,10 +2189,18 @@ impl RuleT for ConditionalEquivalence {
         .into()
     }
     fn get_classifications(&self) -> HashSet<RuleClassification> {
-        [RuleClassification::ConditionalEquivalence]
-            .iter()
-            .cloned()
-            .collect()
+        let mut ret: HashSet<RuleClassification> =
+            [RuleClassification::ConditionalEquivalence].iter().cloned().collect();
+        // Implication encodes A -> B === ~A | B; the ~A | B -> A -> B direction is constructive,
+        // but A -> B -> ~A | B relies on excluded middle. Contraposition (A -> B === ~B -> ~A)
+        // has the same asymmetry: deriving ~B -> ~A from A -> B is constructive, but recovering
+        // A -> B from ~B -> ~A needs double-negation elimination on A. Both rules are checked as
+        // an undirected equivalence, so there's no way to allow just the constructive direction;
+        // block the whole rule in intuitionistic mode instead.
+        if matches!(self, ConditionalEquivalence::Implication | ConditionalEquivalence::Contraposition) {
+            ret.insert(RuleClassification::Classical);
+        }
+        ret
     }
     fn num_deps(&self) -> Option<usize> {
         Some(1)
@@ -1435,6 +2214,7 @@ impl RuleT for ConditionalEquivalence {
         conclusion: Expr,
         deps: Vec<PJRef<P>>,
         _sdeps: Vec<P::SubproofReference>,
+        _mode: LogicMode,
     ) -> Result<(), ProofCheckError<PJRef<P>, P::SubproofReference>> {
         use ConditionalEquivalence::*;
         match self {
@@ -1541,10 +2321,13 @@ impl RuleT for RedundantPrepositionalInference {
         .into()
     }
     fn get_classifications(&self) -> HashSet<RuleClassification> {
-        [RuleClassification::MiscInference]
-            .iter()
-            .cloned()
-            .collect()
+        let mut ret: HashSet<RuleClassification> =
+            [RuleClassification::MiscInference].iter().cloned().collect();
+        // ExcludedMiddle (A | ~A) is the law of excluded middle itself.
+        if let RedundantPrepositionalInference::ExcludedMiddle = self {
+            ret.insert(RuleClassification::Classical);
+        }
+        ret
     }
     fn num_deps(&self) -> Option<usize> {
         use RedundantPrepositionalInference::*;
@@ -1563,6 +2346,7 @@ impl RuleT for RedundantPrepositionalInference {
         conclusion: Expr,
         deps: Vec<PJRef<P>>,
         sdeps: Vec<P::SubproofReference>,
+        _mode: LogicMode,
     ) -> Result<(), ProofCheckError<PJRef<P>, P::SubproofReference>> {
         use ProofCheckError::*;
         use RedundantPrepositionalInference::*;
@@ -1749,12 +2533,44 @@ impl RuleT for RedundantPrepositionalInference {
     }
 }
 
+/// The logical negation of a literal: strips a leading `Not`, or adds one. Used by
+/// `AsymmetricTautology`'s unit-propagation check, which needs to flip literals rather than
+/// evaluate them.
+fn negate_literal(e: &Expr) -> Expr {
+    if let Expr::Not { operand } = e {
+        (**operand).clone()
+    } else {
+        Expr::Not { operand: Box::new(e.clone()) }
+    }
+}
+
+/// A machine-checkable witness for *why* an `AutomationRelatedRules` step holds, populated by
+/// `check_with_cert` alongside (not instead of) the usual `Ok(())`/`Err` verdict `check` gives.
+/// Lets downstream tooling re-verify the inference independently of aris, or the GUI render the
+/// concrete derivation instead of a bare pass/fail.
+#[derive(Debug, Clone)]
+pub enum RuleCertificate {
+    /// `Resolution`: resolving `left` and `right` on `pivot` (present in `left`, negated in
+    /// `right`) yields the conclusion.
+    ResolutionStep { pivot: Expr, left: Vec<Expr>, right: Vec<Expr> },
+    /// `AsymmetricTautology`: negating the conclusion's disjuncts and unit-propagating them
+    /// through the premise clauses forces `chain`, in order, ending in a conflict -- some literal
+    /// and its negation both in `chain`, or a premise clause every one of whose literals has its
+    /// negation in `chain`.
+    UnitPropagation { chain: Vec<Expr> },
+    /// `TautologicalConsequence`'s propositional path: `clauses` (the premises' disjuncts, plus
+    /// the conclusion's disjuncts negated into units) are jointly unsatisfiable.
+    SatRefutation { clauses: Vec<Vec<Expr>> },
+}
+
 impl RuleT for AutomationRelatedRules {
     fn get_name(&self) -> String {
         match self {
             AutomationRelatedRules::AsymmetricTautology => "Asymmetric Tautology",
             AutomationRelatedRules::Resolution => "Resolution",
             AutomationRelatedRules::TautologicalConsequence => "Tautological Consequence",
+            AutomationRelatedRules::Prover => "Prover",
+            AutomationRelatedRules::Tautology => "Tautology",
         }
         .into()
     }
@@ -1769,13 +2585,17 @@ impl RuleT for AutomationRelatedRules {
             AutomationRelatedRules::AsymmetricTautology => None,
             AutomationRelatedRules::Resolution => Some(2),
             AutomationRelatedRules::TautologicalConsequence => None,
+            AutomationRelatedRules::Prover => None,
+            AutomationRelatedRules::Tautology => Some(0),
         }
     }
     fn num_subdeps(&self) -> Option<usize> {
         match self {
             AutomationRelatedRules::AsymmetricTautology
             | AutomationRelatedRules::Resolution
-            | AutomationRelatedRules::TautologicalConsequence => Some(0),
+            | AutomationRelatedRules::TautologicalConsequence
+            | AutomationRelatedRules::Prover
+            | AutomationRelatedRules::Tautology => Some(0),
         }
     }
     fn check<P: Proof>(
@@ -1783,94 +2603,499 @@ impl RuleT for AutomationRelatedRules {
         p: &P,
         conclusion: Expr,
         deps: Vec<PJRef<P>>,
-        _sdeps: Vec<P::SubproofReference>,
+        sdeps: Vec<P::SubproofReference>,
+        mode: LogicMode,
     ) -> Result<(), ProofCheckError<PJRef<P>, P::SubproofReference>> {
+        self.check_with_cert(p, conclusion, deps, sdeps, mode)?;
+        Ok(())
+    }
+    fn check_with_cert<P: Proof>(
+        self,
+        p: &P,
+        conclusion: Expr,
+        deps: Vec<PJRef<P>>,
+        _sdeps: Vec<P::SubproofReference>,
+        mode: LogicMode,
+    ) -> Result<Option<RuleCertificate>, ProofCheckError<PJRef<P>, P::SubproofReference>> {
         match self {
-            AutomationRelatedRules::AsymmetricTautology => unimplemented!(),
+            AutomationRelatedRules::AsymmetricTautology => {
+                // Reverse unit propagation (RUP), the same check SAT proof checkers use to
+                // validate a learned clause: treat each dependency's disjuncts() as a premise
+                // clause (F), and the conclusion's disjuncts() as the clause to derive (C).
+                // Assert the negation of every literal of C as a unit, then propagate to a
+                // fixpoint: whenever a clause of F has every literal but one falsified, force
+                // that literal true. C is an asymmetric tautology w.r.t. F exactly when this
+                // reaches a conflict (some clause of F is fully falsified, or the negated
+                // literals of C contradict each other).
+                let dep_exprs = deps
+                    .iter()
+                    .map(|d| p.lookup_expr_or_die(d))
+                    .collect::<Result<Vec<Expr>, _>>()?;
+                // A clause with a literal and its negation is a trivial tautology: it can never
+                // be falsified, so it never contributes a conflict and is dropped up front.
+                let clauses: Vec<Vec<Expr>> = dep_exprs
+                    .iter()
+                    .map(|e| e.disjuncts())
+                    .filter(|clause| !clause.iter().any(|lit| clause.contains(&negate_literal(lit))))
+                    .collect();
+                let mut assigned_true: HashSet<Expr> = HashSet::new();
+                // `chain`, unlike `assigned_true`, remembers the order literals were forced in, so
+                // it can be handed out as `RuleCertificate::UnitPropagation`'s witness.
+                let mut chain: Vec<Expr> = Vec::new();
+                for lit in conclusion.disjuncts() {
+                    let negated = negate_literal(&lit);
+                    if assigned_true.insert(negated.clone()) {
+                        chain.push(negated);
+                    }
+                }
+                // C may already be self-contradictory (e.g. it's itself a tautological clause
+                // like A | ~A), which shows up here as both a literal and its negation getting
+                // asserted true from the same negation step.
+                let mut conflict = assigned_true.iter().any(|lit| assigned_true.contains(&negate_literal(lit)));
+                while !conflict {
+                    let mut to_propagate = None;
+                    for clause in &clauses {
+                        if clause.iter().any(|lit| assigned_true.contains(lit)) {
+                            continue; // already satisfied
+                        }
+                        let undetermined: Vec<&Expr> = clause
+                            .iter()
+                            .filter(|lit| !assigned_true.contains(&negate_literal(lit)))
+                            .collect();
+                        match undetermined.len() {
+                            0 => {
+                                conflict = true;
+                                break;
+                            }
+                            1 if to_propagate.is_none() => to_propagate = Some(undetermined[0].clone()),
+                            _ => {}
+                        }
+                    }
+                    if conflict {
+                        break;
+                    }
+                    match to_propagate {
+                        Some(lit) => {
+                            assigned_true.insert(lit.clone());
+                            chain.push(lit);
+                        }
+                        None => break, // fixpoint reached, no conflict found
+                    }
+                }
+                if conflict {
+                    Ok(Some(RuleCertificate::UnitPropagation { chain }))
+                } else {
+                    let unresolved = clauses
+                        .iter()
+                        .filter(|clause| !clause.iter().any(|lit| assigned_true.contains(lit)))
+                        .count();
+                    Err(ProofCheckError::Other(format!(
+                        "Not an asymmetric tautology: unit propagation reached a fixpoint without a conflict ({} premise clause(s) still unresolved after negating the conclusion).",
+                        unresolved
+                    )))
+                }
+            }
             AutomationRelatedRules::Resolution => {
+                // Standard clausal resolution over the two premises' disjuncts (C1, C2): find a
+                // pivot literal l with l ∈ C1 and ¬l ∈ C2 (via `negate_literal`, the same
+                // literal-flip `AsymmetricTautology` uses), and accept iff the conclusion's
+                // disjuncts equal (C1 \ {l}) ∪ (C2 \ {¬l}), up to reordering and duplicates. There
+                // can be more than one pivot candidate (e.g. C1 = {P, Q}, C2 = {¬P, ¬Q}); any one
+                // of them producing the stated conclusion is accepted, rather than the old check's
+                // "the symmetric difference must be exactly 2 contradicting expressions", which
+                // only ever matched the single-pivot case.
                 let prem0 = p.lookup_expr_or_die(&deps[0])?;
                 let prem1 = p.lookup_expr_or_die(&deps[1])?;
-                let mut premise_disjuncts = HashSet::new();
-                premise_disjuncts.extend(prem0.disjuncts());
-                premise_disjuncts.extend(prem1.disjuncts());
-                let conclusion_disjuncts = HashSet::from_iter(conclusion.disjuncts().into_iter());
-                let mut remainder = premise_disjuncts
-                    .difference(&conclusion_disjuncts)
+                let c1: HashSet<Expr> = prem0.disjuncts().into_iter().collect();
+                let c2: HashSet<Expr> = prem1.disjuncts().into_iter().collect();
+                let conclusion_disjuncts: HashSet<Expr> = conclusion.disjuncts().into_iter().collect();
+
+                let pivots: Vec<Expr> = c1
+                    .iter()
+                    .filter(|l| c2.contains(&negate_literal(l)))
                     .cloned()
-                    .collect::<Vec<Expr>>();
-                //println!("resolution remainder of {:?} and {:?} is {:?}", premise_disjuncts, conclusion_disjuncts, remainder);
-                remainder.sort();
-                match &remainder[..] {
-                    [e1, e2] => do_expressions_contradict::<P>(e1, e2),
-                    _ => {
-                        let mut pretty_remainder: String = "{".into();
-                        for (i, expr) in remainder.iter().enumerate() {
-                            pretty_remainder += &format!(
-                                "{}{}",
-                                expr,
-                                if i != remainder.len() - 1 { ", " } else { "" }
-                            );
-                        }
-                        pretty_remainder += "}";
-                        Err(ProofCheckError::Other(format!("Difference between premise disjuncts and conclusion disjuncts ({}) should be exactly 2 expressions that produce a contradiction.", pretty_remainder)))
-                    }
+                    .collect();
+
+                let resolvent_for = |pivot: &Expr| -> HashSet<Expr> {
+                    let neg_pivot = negate_literal(pivot);
+                    c1.iter()
+                        .filter(|e| *e != pivot)
+                        .chain(c2.iter().filter(|e| *e != &neg_pivot))
+                        .cloned()
+                        .collect()
+                };
+
+                if let Some(pivot) = pivots.iter().find(|pivot| resolvent_for(pivot) == conclusion_disjuncts) {
+                    Ok(Some(RuleCertificate::ResolutionStep {
+                        pivot: pivot.clone(),
+                        left: prem0.disjuncts(),
+                        right: prem1.disjuncts(),
+                    }))
+                } else if pivots.is_empty() {
+                    Err(ProofCheckError::Other(format!(
+                        "No literal in {} is the negation of a literal in {}; resolution needs one clause to contain some l and the other ¬l.",
+                        prem0, prem1
+                    )))
+                } else {
+                    Err(ProofCheckError::Other(format!(
+                        "None of the candidate pivot literals ({}) resolve {} and {} into {}.",
+                        pivots.iter().map(|e| e.to_string()).collect::<Vec<_>>().join(", "),
+                        prem0,
+                        prem1,
+                        conclusion
+                    )))
                 }
             }
             AutomationRelatedRules::TautologicalConsequence => {
-                // Closure for making CNF conversion errors
-                let cnf_error = || {
-                    ProofCheckError::Other("Failed converting to CNF; the propositions for this rule should not use quantifiers, arithmetic, or application.".to_string())
-                };
-
-                // Closure to convert expression into CNF and change to result type
-                let into_cnf = |expr: Expr| expr.into_cnf().ok_or_else(cnf_error);
-
-                // Convert the premises to a single expression by AND-ing them together
-                let premises = deps
+                // Accepted iff (d1 /\ ... /\ dn) -> conclusion is a tautology, decided by a
+                // native signed-formula tableau search (`tableau::tableau_prove`) rather than
+                // Tseitin-encoding to CNF and calling out to the DPLL solver: seed the tableau
+                // with every dependency on the left (negative/assumed) and the conclusion on the
+                // right (positive/to prove), and the step succeeds iff every resulting branch
+                // closes. Non-propositional subformulas (predicate applications, quantified
+                // formulas) are left as opaque atoms by `tableau::is_atomic`, so this still works
+                // over the full language rather than rejecting anything outside the boolean
+                // fragment, exactly as the Tseitin encoding did. Unlike that SAT call, this
+                // leaves behind an actual derivation -- `RuleT::check` has no channel to return
+                // it to the caller, so it's discarded below, but the same `tableau_prove` is
+                // there to be reused by anything that does want to render it.
+                let dep_exprs = deps
                     .into_iter()
                     .map(|dep| p.lookup_expr_or_die(&dep))
                     .collect::<Result<Vec<Expr>, _>>()?;
-                let premise = Expr::Assoc {
-                    op: Op::And,
-                    exprs: premises,
-                };
 
-                // Create `varisat` formula of `~(P -> Q)`. If this is
-                // unsatisfiable, then we've proven `P -> Q`.
-                let sat = Expr::not(Expr::implies(premise, conclusion));
-                let (sat, vars) = into_cnf(sat)?.to_varisat();
-                let mut solver = varisat::Solver::new();
-                solver.add_formula(&sat);
+                // A propositional SAT/tableau check is unsound for intuitionistic validity (it
+                // assumes excluded middle implicitly), so in `Intuitionistic` mode this swaps to
+                // `lj_provable`, the same decomposition restricted to LJ's single-succedent
+                // sequents, instead of either of the classical backends below.
+                if mode == LogicMode::Intuitionistic {
+                    return match lj_provable(dep_exprs, vec![conclusion]) {
+                        Ok(()) => Ok(None),
+                        Err((antecedent, succedent)) => Err(ProofCheckError::Other(format!(
+                            "Not true by tautological consequence: this step is not valid intuitionistically (the sequent {} ⊢ {} is open).",
+                            pretty_sequent(&antecedent),
+                            pretty_sequent(&succedent)
+                        ))),
+                    };
+                }
 
-                // Does not panic on the default config
-                solver.solve().expect("varisat error");
+                // The tableau's opaque-atom treatment of quantifiers/application is sound but
+                // incomplete, so when the step actually depends on first-order reasoning -- and
+                // an ATP is configured via the `tptp` feature -- fall back to `tptp::discharge`
+                // instead of failing a step that's genuinely first-order valid. This mirrors
+                // `Prover`'s feature gate, but stays off the hot path: most steps are purely
+                // propositional and should never pay for spawning an external process.
+                #[cfg(feature = "tptp")]
+                if dep_exprs.iter().any(crate::tptp::needs_first_order) || crate::tptp::needs_first_order(&conclusion) {
+                    let config = crate::tptp::SolverConfig::from_env();
+                    return match crate::tptp::discharge(&dep_exprs, &conclusion, &config) {
+                        Ok(crate::tptp::ProverResult::Unsat) => Ok(None),
+                        Ok(crate::tptp::ProverResult::Sat) => Err(ProofCheckError::Other(
+                            "Not true by tautological consequence: the external prover found the premises satisfiable with the conclusion false.".to_string(),
+                        )),
+                        Ok(crate::tptp::ProverResult::Unknown) => Err(ProofCheckError::Other(
+                            "The external prover couldn't determine whether this step is valid by tautological consequence.".to_string(),
+                        )),
+                        Err(e) => Err(ProofCheckError::Other(format!("Couldn't check tautological consequence via the external prover: {}", e))),
+                    };
+                }
+
+                // Build the `SatRefutation` clause set before `dep_exprs` is consumed below: the
+                // premises' own disjuncts, plus the conclusion's disjuncts negated into unit
+                // clauses -- the same shape `AsymmetricTautology`'s clauses are in, just certified
+                // unsatisfiable by the tableau refutation below rather than by RUP.
+                let mut clauses: Vec<Vec<Expr>> = dep_exprs.iter().map(|e| e.disjuncts()).collect();
+                clauses.extend(conclusion.disjuncts().iter().map(|lit| vec![negate_literal(lit)]));
+
+                let mut signed: Vec<tableau::SignedFormula> =
+                    dep_exprs.into_iter().map(|e| (e, false)).collect();
+                signed.push((conclusion, true));
+                match tableau::tableau_prove(signed) {
+                    Ok(_proof) => Ok(Some(RuleCertificate::SatRefutation { clauses })),
+                    Err(open_branch) => Err(ProofCheckError::Other(format!(
+                        "Not true by tautological consequence: the assignment {} is a countermodel (every dependency holds, the conclusion fails).",
+                        tableau::pretty_open_branch(&open_branch)
+                    ))),
+                }
+            }
+            AutomationRelatedRules::Prover => {
+                // Unlike the other rules in this enum, `Prover` doesn't decide anything itself:
+                // it hands `deps` and the negation of `conclusion` to an external SMT solver as
+                // an SMT-LIB2 script (see `smt::build_query`) and accepts the step iff the
+                // solver reports `unsat`. This covers predicate applications and quantifiers
+                // precisely (as uninterpreted functions and real binders) rather than the
+                // propositional abstraction `TautologicalConsequence` falls back to, at the cost
+                // of needing a solver binary on hand -- hence the `smt` feature gate, so a build
+                // with no solver installed still compiles and every other rule still works.
+                #[cfg(feature = "smt")]
+                {
+                    let dep_exprs = deps
+                        .iter()
+                        .map(|d| p.lookup_expr_or_die(d))
+                        .collect::<Result<Vec<Expr>, _>>()?;
+                    let config = crate::smt::SolverConfig::from_env();
+                    match crate::smt::discharge(&dep_exprs, &conclusion, &config) {
+                        Ok(crate::smt::ProverResult::Unsat) => Ok(None),
+                        Ok(crate::smt::ProverResult::Sat(model)) => {
+                            Err(ProofCheckError::Other(match model {
+                                Some(model) => format!("The external prover found a countermodel where every dependency holds and the conclusion fails:\n{}", model),
+                                None => "The external prover found a countermodel where every dependency holds and the conclusion fails.".to_string(),
+                            }))
+                        }
+                        Ok(crate::smt::ProverResult::Unknown) => Err(ProofCheckError::Other(
+                            "The external prover returned `unknown` instead of deciding the step.".to_string(),
+                        )),
+                        Err(e) => Err(ProofCheckError::Other(format!("External prover error: {}", e))),
+                    }
+                }
+                #[cfg(not(feature = "smt"))]
+                {
+                    Err(ProofCheckError::Other(
+                        "This build was compiled without the `smt` feature, so Prover (which shells out to an external SMT solver) is unavailable.".to_string(),
+                    ))
+                }
+            }
+            AutomationRelatedRules::Tautology => {
+                // Accepted iff `conclusion` is a propositional tautology, decided by LK-style
+                // sequent decomposition rather than truth tables (cf. `ContradictionIntro`'s
+                // Tseitin/DPLL route, which is exponentially cheaper but less legible -- this
+                // rule is for when the *proof-theoretic* argument matters).
+                match lk_provable(vec![], vec![conclusion]) {
+                    Ok(()) => Ok(None),
+                    Err((antecedent, succedent)) => Err(ProofCheckError::Other(format!(
+                        "Not a tautology: the sequent {} ⊢ {} is falsifiable (every formula there is atomic, and none occurs on both sides).",
+                        pretty_sequent(&antecedent),
+                        pretty_sequent(&succedent)
+                    ))),
+                }
+            }
+        }
+    }
+}
 
-                // If unsatisfiable, we know `P -> Q`
-                match solver.model() {
-                    Some(model) => {
-                        // Satisfiable, so `P -> Q` is false. The counterexample is `model`.
+/// Render a sequent side (antecedent or succedent) as a comma-separated list, for the `Tautology`
+/// error message.
+fn pretty_sequent(exprs: &[Expr]) -> String {
+    exprs.iter().map(|e| e.to_string()).collect::<Vec<_>>().join(", ")
+}
 
-                        // Convert model to human-readable variable assignments
-                        // for an error message
-                        let model = model
-                            .into_iter()
-                            .map(|lit| {
-                                let name = vars.get(&lit.var()).expect("taut con vars map error");
-                                let val = if lit.is_positive() { 'T' } else { 'F' };
-                                format!("{} = {}", name, val)
-                            })
-                            .collect::<Vec<String>>()
-                            .join(", ");
+/// True iff `e` isn't decomposed further by `lk_provable`'s propositional rules -- i.e. it's a
+/// leaf as far as this calculus is concerned (an atom, `Contra`, a quantified formula, or a
+/// biconditional/equality, none of which this rule's connective set covers).
+fn lk_is_atomic(e: &Expr) -> bool {
+    !matches!(
+        e,
+        Expr::Not { .. } | Expr::Impl { .. } | Expr::Assoc { op: Op::And | Op::Or, .. }
+    )
+}
 
-                        Err(ProofCheckError::Other(format!(
-                            "Not true by tautological consequence; Counterexample: {}",
-                            model
-                        )))
+/// Decide whether the sequent `antecedent ⊢ succedent` is provable in (propositional) LK using
+/// only its invertible rules, so no backtracking is needed: at each step, either the sequent is
+/// already an axiom (some formula occurs on both sides, or `Contra` occurs in `antecedent`), or
+/// some non-atomic formula is decomposed per its connective (splitting into two recursive
+/// subgoals for right-`∧`/left-`∨`-style branching rules, one subgoal otherwise) until every
+/// formula is atomic. Returns the first such non-axiom leaf sequent as a witness when the
+/// original sequent isn't provable -- the falsifying assignment is exactly "every antecedent atom
+/// true, every succedent atom false".
+fn lk_provable(mut antecedent: Vec<Expr>, mut succedent: Vec<Expr>) -> Result<(), (Vec<Expr>, Vec<Expr>)> {
+    if antecedent.contains(&Expr::Contra) || antecedent.iter().any(|a| succedent.contains(a)) {
+        return Ok(());
+    }
+    if let Some(i) = antecedent.iter().position(|e| !lk_is_atomic(e)) {
+        let formula = antecedent.remove(i);
+        return match formula {
+            // left-¬: ¬A, Γ ⊢ Δ  is provable iff  Γ ⊢ A, Δ
+            Expr::Not { operand } => {
+                succedent.push(*operand);
+                lk_provable(antecedent, succedent)
+            }
+            // left-∧: A ∧ B, Γ ⊢ Δ  is provable iff  A, B, Γ ⊢ Δ
+            Expr::Assoc { op: Op::And, exprs } => {
+                antecedent.extend(exprs);
+                lk_provable(antecedent, succedent)
+            }
+            // left-∨: A ∨ B, Γ ⊢ Δ  is provable iff both  A, Γ ⊢ Δ  and  B, Γ ⊢ Δ  are
+            Expr::Assoc { op: Op::Or, exprs } => {
+                for e in exprs {
+                    let mut antecedent = antecedent.clone();
+                    antecedent.push(e);
+                    lk_provable(antecedent, succedent.clone())?;
+                }
+                Ok(())
+            }
+            // left-→: A -> B, Γ ⊢ Δ  is provable iff both  Γ ⊢ A, Δ  and  B, Γ ⊢ Δ  are
+            Expr::Impl { left, right } => {
+                let mut succedent_for_left = succedent.clone();
+                succedent_for_left.push(*left);
+                lk_provable(antecedent.clone(), succedent_for_left)?;
+                antecedent.push(*right);
+                lk_provable(antecedent, succedent)
+            }
+            other => unreachable!("lk_is_atomic said {} wasn't atomic", other),
+        };
+    }
+    if let Some(i) = succedent.iter().position(|e| !lk_is_atomic(e)) {
+        let formula = succedent.remove(i);
+        return match formula {
+            // right-¬: Γ ⊢ ¬A, Δ  is provable iff  A, Γ ⊢ Δ
+            Expr::Not { operand } => {
+                antecedent.push(*operand);
+                lk_provable(antecedent, succedent)
+            }
+            // right-∨: Γ ⊢ A ∨ B, Δ  is provable iff  Γ ⊢ A, B, Δ
+            Expr::Assoc { op: Op::Or, exprs } => {
+                succedent.extend(exprs);
+                lk_provable(antecedent, succedent)
+            }
+            // right-∧: Γ ⊢ A ∧ B, Δ  is provable iff both  Γ ⊢ A, Δ  and  Γ ⊢ B, Δ  are
+            Expr::Assoc { op: Op::And, exprs } => {
+                for e in exprs {
+                    let mut succedent = succedent.clone();
+                    succedent.push(e);
+                    lk_provable(antecedent.clone(), succedent)?;
+                }
+                Ok(())
+            }
+            // right-→: Γ ⊢ A -> B, Δ  is provable iff  A, Γ ⊢ B, Δ
+            Expr::Impl { left, right } => {
+                antecedent.push(*left);
+                succedent.push(*right);
+                lk_provable(antecedent, succedent)
+            }
+            other => unreachable!("lk_is_atomic said {} wasn't atomic", other),
+        };
+    }
+    // Every formula is atomic and this isn't an axiom: falsifiable by making every antecedent
+    // atom true and every succedent atom false.
+    Err((antecedent, succedent))
+}
+
+/// Decide whether the sequent `antecedent ⊢ succedent` is provable in intuitionistic (LJ)
+/// sequent calculus, maintaining LJ's single-succedent invariant (`succedent` has at most one
+/// formula at every step, enforced by the caller and preserved below) rather than `lk_provable`'s
+/// unrestricted classical one.
+///
+/// Unlike `lk_provable`, the order rules are tried in matters here: not every LJ rule is
+/// invertible (safe to apply eagerly without ever needing to backtrack past it). left-→ and
+/// right-∨ both require committing to a choice -- proving `Γ ⊢ A` standalone to discharge
+/// left-→'s antecedent throws away whatever else is in scope for later, and right-∨ picks one
+/// disjunct to commit to -- so applying either before the invertible rules around them have been
+/// exhausted can foreclose a proof that only goes through in a different order. Concretely,
+/// `(P -> (P -> Q)) -> (P -> Q)` needs right-→ applied *before* the antecedent's `P -> (P -> Q)`
+/// is touched at all; decomposing that left-→ first demands proving `⊢ P` in isolation, which
+/// fails. Dyckhoff's LJT calculus handles this by saturating every invertible rule first and
+/// only falling back to the non-invertible ones once none applies, which is what the three
+/// phases below do:
+///   1. invertible antecedent rules: left-¬, left-∧, left-∨ (everything but left-→)
+///   2. invertible succedent rules: right-¬, right-∧, right-→ (everything but right-∨)
+///   3. non-invertible fallback: left-→, then right-∨
+fn lj_provable(mut antecedent: Vec<Expr>, mut succedent: Vec<Expr>) -> Result<(), (Vec<Expr>, Vec<Expr>)> {
+    if antecedent.contains(&Expr::Contra) || succedent.iter().any(|s| antecedent.contains(s)) {
+        return Ok(());
+    }
+    // Phase 1: saturate the invertible antecedent rules. left-→ is deliberately excluded (see
+    // above) and left for phase 3.
+    if let Some(i) = antecedent.iter().position(|e| matches!(e, Expr::Not { .. } | Expr::Assoc { op: Op::And | Op::Or, .. })) {
+        let formula = antecedent.remove(i);
+        return match formula {
+            // left-¬: ¬A, Γ ⊢ Δ  is provable iff  Γ ⊢ A
+            Expr::Not { operand } => lj_provable(antecedent, vec![*operand]),
+            // left-∧: A ∧ B, Γ ⊢ Δ  is provable iff  A, B, Γ ⊢ Δ
+            Expr::Assoc { op: Op::And, exprs } => {
+                antecedent.extend(exprs);
+                lj_provable(antecedent, succedent)
+            }
+            // left-∨: A ∨ B, Γ ⊢ Δ  is provable iff both  A, Γ ⊢ Δ  and  B, Γ ⊢ Δ  are
+            Expr::Assoc { op: Op::Or, exprs } => {
+                for e in exprs {
+                    let mut antecedent = antecedent.clone();
+                    antecedent.push(e);
+                    lj_provable(antecedent, succedent.clone())?;
+                }
+                Ok(())
+            }
+            other => unreachable!("only left-¬/∧/∨ were matched above, got {}", other),
+        };
+    }
+    // Phase 2: saturate the invertible succedent rule, if the lone succedent formula has one.
+    // right-∨ is deliberately excluded (see above) and left for phase 3.
+    if let Some(formula) = succedent.first() {
+        if !lk_is_atomic(formula) && !matches!(formula, Expr::Assoc { op: Op::Or, .. }) {
+            let formula = succedent.remove(0);
+            return match formula {
+                // right-¬: Γ ⊢ ¬A  is provable iff  A, Γ ⊢  (an empty succedent -- nothing further
+                // to prove once `antecedent` is contradictory, which this connective-restricted
+                // calculus can only witness via a literal `Contra`).
+                Expr::Not { operand } => {
+                    antecedent.push(*operand);
+                    lj_provable(antecedent, vec![])
+                }
+                // right-∧: Γ ⊢ A ∧ B  is provable iff both  Γ ⊢ A  and  Γ ⊢ B  are
+                Expr::Assoc { op: Op::And, exprs } => {
+                    for e in exprs {
+                        lj_provable(antecedent.clone(), vec![e])?;
                     }
-                    None => Ok(()),
+                    Ok(())
                 }
+                // right-→: Γ ⊢ A -> B  is provable iff  A, Γ ⊢ B
+                Expr::Impl { left, right } => {
+                    antecedent.push(*left);
+                    lj_provable(antecedent, vec![*right])
+                }
+                other => unreachable!("only right-¬/∧/→ were matched above, got {}", other),
+            };
+        }
+    }
+    // Phase 3: neither side has an invertible rule left to apply -- fall back to the
+    // non-invertible ones, preferring left-→ (matching `lk_provable`'s left-before-right order)
+    // since by this point it's the only antecedent rule that could still apply. Which antecedent
+    // implication to decompose isn't determined by citation order: e.g.
+    // {p→q, (p→q)→p} ⊢ p needs (p→q)→p picked first (decomposing p→q first demands proving
+    // `⊢ p` on its own, which fails), so every candidate implication is tried in turn, with a
+    // failed attempt backtracking to try the next one rather than committing to the first found.
+    let impl_positions: Vec<usize> = antecedent
+        .iter()
+        .enumerate()
+        .filter(|(_, e)| matches!(e, Expr::Impl { .. }))
+        .map(|(i, _)| i)
+        .collect();
+    if !impl_positions.is_empty() {
+        let mut last_err = None;
+        for i in impl_positions {
+            let mut antecedent = antecedent.clone();
+            let (left, right) = match antecedent.remove(i) {
+                // left-→: A -> B, Γ ⊢ Δ  is provable iff  Γ ⊢ A  and  B, Γ ⊢ Δ
+                Expr::Impl { left, right } => (left, right),
+                other => unreachable!("only left-→ was matched above, got {}", other),
+            };
+            let attempt = lj_provable(antecedent.clone(), vec![*left]).and_then(|()| {
+                antecedent.push(*right);
+                lj_provable(antecedent, succedent.clone())
+            });
+            match attempt {
+                Ok(()) => return Ok(()),
+                Err(open) => last_err = Some(open),
             }
         }
+        return Err(last_err.expect("impl_positions is non-empty, so the loop ran at least once"));
+    }
+    match succedent.first() {
+        // Nothing left to prove and no axiom above closed it: this (sub)goal is open.
+        None => Err((antecedent, succedent)),
+        Some(formula) if lk_is_atomic(formula) => Err((antecedent, succedent)),
+        // right-∨: Γ ⊢ A ∨ B  is provable iff  Γ ⊢ A  or  Γ ⊢ B
+        Some(Expr::Assoc { op: Op::Or, exprs }) => {
+            let exprs = exprs.clone();
+            let mut last_err = None;
+            for e in exprs {
+                match lj_provable(antecedent.clone(), vec![e]) {
+                    Ok(()) => return Ok(()),
+                    Err(open) => last_err = Some(open),
+                }
+            }
+            Err(last_err.unwrap_or((antecedent, succedent)))
+        }
+        Some(other) => unreachable!("only right-∨ remains at this point, got {}", other),
     }
 }
 
@@ -1885,14 +3110,30 @@ impl RuleT for QuantifierEquivalence {
             AristoteleanSquare => "Aristotelean Square of Opposition",
             QuantifierDistribution => "Quantifier Distribution",
             PrenexLaws => "Prenex Laws",
+            OnePointRule => "One-Point Rule",
         }
         .into()
     }
     fn get_classifications(&self) -> HashSet<RuleClassification> {
-        [RuleClassification::QuantifierEquivalence]
-            .iter()
-            .cloned()
-            .collect()
+        let mut ret: HashSet<RuleClassification> =
+            [RuleClassification::QuantifierEquivalence].iter().cloned().collect();
+        // AristoteleanSquare infers existential import (e.g. "all A are B" to "some A is B"),
+        // which assumes nonempty domains and isn't intuitionistically valid. QuantifierNegation
+        // and QuantifierDistribution are each half classical: ¬∀xP(x) ≡ ∃x¬P(x) and
+        // ¬(P∧Q) ≡ ¬P∨¬Q only hold in the ∃x¬P(x) -> ¬∀xP(x) / ¬P∨¬Q -> ¬(P∧Q) direction intuitionistically,
+        // but `check_by_normalize_first_expr` compares both sides after normalizing, so it can't
+        // tell which direction the user invoked the rule in -- same bind `DoubleNegation` is in,
+        // so the whole rule is classified `Classical` rather than silently accepting the
+        // classical-only direction.
+        if matches!(
+            self,
+            QuantifierEquivalence::AristoteleanSquare
+                | QuantifierEquivalence::QuantifierNegation
+                | QuantifierEquivalence::QuantifierDistribution
+        ) {
+            ret.insert(RuleClassification::Classical);
+        }
+        ret
     }
     fn num_deps(&self) -> Option<usize> {
         Some(1)
@@ -1906,6 +3147,7 @@ impl RuleT for QuantifierEquivalence {
         conclusion: Expr,
         deps: Vec<PJRef<P>>,
         _sdeps: Vec<P::SubproofReference>,
+        _mode: LogicMode,
     ) -> Result<(), ProofCheckError<PJRef<P>, P::SubproofReference>> {
         use QuantifierEquivalence::*;
         match self {
@@ -1946,10 +3188,106 @@ impl RuleT for QuantifierEquivalence {
                 false,
                 Expr::normalize_prenex_laws,
             ),
+            OnePointRule => {
+                let prem = p.lookup_expr_or_die(&deps[0])?;
+                let holds = one_point_reduce(&prem)
+                    .map_or(false, |reduced| alpha_equiv(&reduced, &conclusion))
+                    || one_point_reduce(&conclusion)
+                        .map_or(false, |reduced| alpha_equiv(&reduced, &prem));
+                if holds {
+                    Ok(())
+                } else {
+                    Err(ProofCheckError::Other(format!(
+                        "{} and {} are not related by the one-point rule (∃x.(x = t ∧ P) ≡ P[x := t], or the ∀ analogue ∀x.(x = t → P) ≡ P[x := t]) for some term t not containing x.",
+                        prem, conclusion
+                    )))
+                }
+            }
         }
     }
 }
 
+/// Try to reduce `e` via Isabelle's "one-point"/miniscoping simplification: an expression of the
+/// shape `∃x.(x = t ∧ P)` or `∀x.(x = t → P)`, where `t` is a term not containing `x`, reduces to
+/// `P[x := t]`. Returns `None` if `e` isn't of either shape (including when `t` mentions `x`, in
+/// which case the substitution wouldn't be capture-avoiding and the rule doesn't apply).
+fn one_point_reduce(e: &Expr) -> Option<Expr> {
+    let Expr::Quant { kind, name, body } = e else {
+        return None;
+    };
+    let (equality, matrix) = match (kind, &**body) {
+        (QuantKind::Exists, Expr::Assoc { op: Op::And, exprs }) if exprs.len() >= 2 => {
+            let rest = &exprs[1..];
+            let matrix = if rest.len() == 1 {
+                rest[0].clone()
+            } else {
+                Expr::assoc(Op::And, rest)
+            };
+            (exprs[0].clone(), matrix)
+        }
+        (QuantKind::Forall, Expr::Impl { left, right }) => (*left.clone(), *right.clone()),
+        _ => return None,
+    };
+    let (s, t) = match &equality {
+        Expr::Assoc { op: Op::Equiv, exprs } if exprs.len() == 2 => {
+            (exprs[0].clone(), exprs[1].clone())
+        }
+        _ => return None,
+    };
+    let x = Expr::var(name);
+    let t = if s == x && !crate::expr::free_vars(&t).contains(name) {
+        t
+    } else if t == x && !crate::expr::free_vars(&s).contains(name) {
+        s
+    } else {
+        return None;
+    };
+    Some(crate::expr::subst(matrix, name, t))
+}
+
+/// Structural equality up to renaming of bound variables, since `one_point_reduce`'s
+/// substitution may leave a binder whose name differs (but is otherwise equivalent) from the
+/// one the proof author wrote on the other side of the equivalence.
+fn alpha_equiv(a: &Expr, b: &Expr) -> bool {
+    fn go(a: &Expr, b: &Expr, renaming: &mut HashMap<String, String>) -> bool {
+        match (a, b) {
+            (Expr::Var { name: an }, Expr::Var { name: bn }) => {
+                renaming.get(an).map_or(an == bn, |n| n == bn)
+            }
+            (Expr::Not { operand: a }, Expr::Not { operand: b }) => go(a, b, renaming),
+            (Expr::Impl { left: al, right: ar }, Expr::Impl { left: bl, right: br }) => {
+                go(al, bl, renaming) && go(ar, br, renaming)
+            }
+            (Expr::Assoc { op: ao, exprs: ae }, Expr::Assoc { op: bo, exprs: be }) => {
+                ao == bo
+                    && ae.len() == be.len()
+                    && ae.iter().zip(be.iter()).all(|(x, y)| go(x, y, renaming))
+            }
+            (
+                Expr::Quant { kind: ak, name: an, body: ab },
+                Expr::Quant { kind: bk, name: bn, body: bb },
+            ) => {
+                if ak != bk {
+                    return false;
+                }
+                let shadowed = renaming.insert(an.clone(), bn.clone());
+                let result = go(ab, bb, renaming);
+                match shadowed {
+                    Some(prev) => {
+                        renaming.insert(an.clone(), prev);
+                    }
+                    None => {
+                        renaming.remove(an);
+                    }
+                }
+                result
+            }
+            _ => a == b,
+        }
+    }
+    go(a, b, &mut HashMap::new())
+}
+
 impl RuleT for EmptyRule {
     fn get_name(&self) -> String {
         "Rule".to_string()
@@ -1969,6 +3307,7 @@ impl RuleT for EmptyRule {
         _: Expr,
         _: Vec<PJRef<P>>,
         _: Vec<P::SubproofReference>,
+        _mode: LogicMode,
     ) -> Result<(), ProofCheckError<PJRef<P>, P::SubproofReference>> {
         Err(ProofCheckError::Other("No rule selected".to_string()))
     }
@@ -2079,6 +3418,52 @@ where
     any_order(deps, check_func, fallthrough_error)
 }
 
+/// How many of the candidate premise lines `suggest_rules` below considers at all (the rest are
+/// ignored, for every rule); bounds its permutation search.
+const SUGGEST_RULES_MAX_CANDIDATES: usize = 8;
+
+/// Search for one-step ways to justify `goal` from `candidates` (cf. Isabelle's `solve_direct`/
+/// `try`): for each rule in `RuleM::ALL_RULES`, enumerate dependency tuples of the arity given by
+/// `num_deps()` (all orderings, since e.g. `ImpElim`'s deps aren't commutative) and keep the ones
+/// for which `check` succeeds. Used by the GUI to suggest "how can I justify this line?".
+///
+/// Only rules with `num_subdeps() == Some(0)` are considered, since `candidates` is a flat list of
+/// premise lines rather than subproofs. Variadic rules (`num_deps() == None`, e.g. `AndIntro`) are
+/// tried once with every candidate as a dependency, rather than over every subset, to keep the
+/// search linear in the number of rules. The search is also capped at
+/// `SUGGEST_RULES_MAX_CANDIDATES` candidates, since the fixed-arity case is otherwise factorial in
+/// `candidates.len()`.
+pub fn suggest_rules<P: Proof>(
+    p: &P,
+    goal: &Expr,
+    candidates: &[PJRef<P>],
+) -> Vec<(Rule, Vec<PJRef<P>>)> {
+    let candidates = &candidates[..candidates.len().min(SUGGEST_RULES_MAX_CANDIDATES)];
+    let mut suggestions = vec![];
+    for rule in RuleM::ALL_RULES.iter().cloned() {
+        if rule.num_subdeps() != Some(0) {
+            continue;
+        }
+        let dep_tuples: Vec<Vec<PJRef<P>>> = match rule.num_deps() {
+            Some(n) if n <= candidates.len() => candidates.iter().cloned().permutations(n).collect(),
+            Some(_) => continue,
+            None => vec![candidates.to_vec()],
+        };
+        for deps in dep_tuples {
+            if rule
+                .check(p, goal.clone(), deps.clone(), vec![], LogicMode::default())
+                .is_ok()
+            {
+                suggestions.push((rule, deps));
+            }
+        }
+    }
+    // Simplest justifications first, so a student offered these as auto-fill suggestions sees
+    // the fewest-premise way to justify the line before more roundabout ones.
+    suggestions.sort_by_key(|(_, deps)| deps.len());
+    suggestions
+}
+
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum ProofCheckError<R, S> {
     LineDoesNotExist(R),
@@ -2091,6 +3476,14 @@ pub enum ProofCheckError<R, S> {
     DoesNotOccur(Expr, Expr),
     DepDoesNotExist(Expr, bool),
     OneOf(BTreeSet<ProofCheckError<R, S>>),
+    /// A propositional rule rejected the step, and a concrete truth assignment was found that
+    /// satisfies every dependency while falsifying the conclusion. Carries the original error
+    /// message alongside the assignment so the GUI can show both.
+    FalsifiedByAssignment(std::collections::BTreeMap<String, bool>, String),
+    /// The rule named here is classified `RuleClassification::Classical` (it depends on the law
+    /// of excluded middle) and so can't be used while checking a proof in
+    /// `LogicMode::Intuitionistic`.
+    NotConstructive(String),
     Other(String),
 }
 
@@ -2146,6 +3539,20 @@ impl<R: std::fmt::Debug, S: std::fmt::Debug> std::fmt::Display for ProofCheckErr
                 }
                 Ok(())
             }
+            FalsifiedByAssignment(assignment, msg) => {
+                writeln!(f, "{}", msg)?;
+                let pretty = assignment
+                    .iter()
+                    .map(|(k, v)| format!("{}={}", k, v))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                write!(f, "Counterexample: {}", pretty)
+            }
+            NotConstructive(rule_name) => write!(
+                f,
+                "{} relies on the law of excluded middle and cannot be used in intuitionistic mode.",
+                rule_name
+            ),
             Other(msg) => write!(f, "{}", msg),
         }
     }
@@ -2204,4 +3611,376 @@ mod tests {
 
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_eq_subst_matches_replaces_free_occurrence() {
+        // phi = P(a), equation a = b, expect P(a) ~> P(b)
+        let phi = Expr::var("P(a)");
+        let conclusion = Expr::var("P(b)");
+        let from = Expr::var("a");
+        let to = Expr::var("b");
+        // A subterm rewrite inside a flat application string: eq_subst_matches has to split
+        // `P(a)` and `P(b)` into head and arguments and match `a` against `b` there.
+        let mut replaced = false;
+        assert!(eq_subst_matches(
+            &phi,
+            &conclusion,
+            &from,
+            &to,
+            &HashSet::new(),
+            &mut replaced
+        ));
+        assert!(replaced);
+    }
+
+    #[test]
+    fn test_eq_subst_matches_rejects_unrelated_change() {
+        let phi = Expr::var("a");
+        let conclusion = Expr::var("c");
+        let from = Expr::var("a");
+        let to = Expr::var("b");
+        let mut replaced = false;
+        assert!(!eq_subst_matches(
+            &phi,
+            &conclusion,
+            &from,
+            &to,
+            &HashSet::new(),
+            &mut replaced
+        ));
+        assert!(!replaced);
+    }
+
+    #[test]
+    fn test_eq_subst_matches_rejects_capture_under_quantifier() {
+        // phi = forall x, R(x, a); substituting a := x would capture x, so it must be rejected
+        // even though the resulting tree is otherwise shaped like a valid rewrite.
+        let phi = Expr::Quant {
+            kind: QuantKind::Forall,
+            name: "x".to_string(),
+            body: Box::new(Expr::var("R(x, a)")),
+        };
+        let conclusion = Expr::Quant {
+            kind: QuantKind::Forall,
+            name: "x".to_string(),
+            body: Box::new(Expr::var("R(x, x)")),
+        };
+        let from = Expr::var("a");
+        let to = Expr::var("x");
+        let mut replaced = false;
+        assert!(!eq_subst_matches(
+            &phi,
+            &conclusion,
+            &from,
+            &to,
+            &HashSet::new(),
+            &mut replaced
+        ));
+    }
+
+    #[test]
+    fn test_one_point_reduce_exists_and() {
+        // exists x, (x = a & P(x))  ~>  P(a)
+        let e = Expr::Quant {
+            kind: QuantKind::Exists,
+            name: "x".to_string(),
+            body: Box::new(Expr::Assoc {
+                op: Op::And,
+                exprs: vec![
+                    Expr::Assoc {
+                        op: Op::Equiv,
+                        exprs: vec![Expr::var("x"), Expr::var("a")],
+                    },
+                    Expr::var("P(x)"),
+                ],
+            }),
+        };
+        assert_eq!(one_point_reduce(&e), Some(Expr::var("P(a)")));
+    }
+
+    #[test]
+    fn test_one_point_reduce_forall_impl_symmetric_equality() {
+        // forall x, (a = x -> P(x))  ~>  P(a), with the equality written t = x instead of x = t
+        let e = Expr::Quant {
+            kind: QuantKind::Forall,
+            name: "x".to_string(),
+            body: Box::new(Expr::Impl {
+                left: Box::new(Expr::Assoc {
+                    op: Op::Equiv,
+                    exprs: vec![Expr::var("a"), Expr::var("x")],
+                }),
+                right: Box::new(Expr::var("P(x)")),
+            }),
+        };
+        assert_eq!(one_point_reduce(&e), Some(Expr::var("P(a)")));
+    }
+
+    #[test]
+    fn test_one_point_reduce_rejects_t_containing_x() {
+        // exists x, (x = f(x) & P(x)) doesn't fit the one-point shape: f(x) isn't x-free.
+        let e = Expr::Quant {
+            kind: QuantKind::Exists,
+            name: "x".to_string(),
+            body: Box::new(Expr::Assoc {
+                op: Op::And,
+                exprs: vec![
+                    Expr::Assoc {
+                        op: Op::Equiv,
+                        exprs: vec![Expr::var("x"), Expr::var("f(x)")],
+                    },
+                    Expr::var("P(x)"),
+                ],
+            }),
+        };
+        assert_eq!(one_point_reduce(&e), None);
+    }
+
+    #[test]
+    fn test_alpha_equiv_renames_bound_variable() {
+        let a = Expr::Quant {
+            kind: QuantKind::Forall,
+            name: "x".to_string(),
+            body: Box::new(Expr::var("x")),
+        };
+        let b = Expr::Quant {
+            kind: QuantKind::Forall,
+            name: "y".to_string(),
+            body: Box::new(Expr::var("y")),
+        };
+        assert!(alpha_equiv(&a, &b));
+    }
+
+    #[test]
+    fn test_to_cnf_distributes_or_over_and() {
+        // a | (b & c)  ~>  (a | b) & (a | c)
+        let e = Expr::Assoc {
+            op: Op::Or,
+            exprs: vec![
+                Expr::var("a"),
+                Expr::Assoc {
+                    op: Op::And,
+                    exprs: vec![Expr::var("b"), Expr::var("c")],
+                },
+            ],
+        };
+        let expected = Expr::Assoc {
+            op: Op::And,
+            exprs: vec![
+                Expr::Assoc {
+                    op: Op::Or,
+                    exprs: vec![Expr::var("a"), Expr::var("b")],
+                },
+                Expr::Assoc {
+                    op: Op::Or,
+                    exprs: vec![Expr::var("a"), Expr::var("c")],
+                },
+            ],
+        };
+        assert_eq!(to_cnf(e), to_cnf(expected));
+    }
+
+    #[test]
+    fn test_to_cnf_eliminates_implication_and_pushes_negation() {
+        // a -> !b  ~>  !a | !b, already in CNF
+        let e = Expr::Impl {
+            left: Box::new(Expr::var("a")),
+            right: Box::new(Expr::Not { operand: Box::new(Expr::var("b")) }),
+        };
+        let expected = Expr::Assoc {
+            op: Op::Or,
+            exprs: vec![
+                Expr::Not { operand: Box::new(Expr::var("a")) },
+                Expr::Not { operand: Box::new(Expr::var("b")) },
+            ],
+        };
+        assert_eq!(to_cnf(e), flatten_sort_dedup(expected));
+    }
+
+    #[test]
+    fn test_to_dnf_distributes_and_over_or() {
+        // a & (b | c)  ~>  (a & b) | (a & c)
+        let e = Expr::Assoc {
+            op: Op::And,
+            exprs: vec![
+                Expr::var("a"),
+                Expr::Assoc {
+                    op: Op::Or,
+                    exprs: vec![Expr::var("b"), Expr::var("c")],
+                },
+            ],
+        };
+        let expected = Expr::Assoc {
+            op: Op::Or,
+            exprs: vec![
+                Expr::Assoc {
+                    op: Op::And,
+                    exprs: vec![Expr::var("a"), Expr::var("b")],
+                },
+                Expr::Assoc {
+                    op: Op::And,
+                    exprs: vec![Expr::var("a"), Expr::var("c")],
+                },
+            ],
+        };
+        assert_eq!(to_dnf(e), to_dnf(expected));
+    }
+
+    #[test]
+    fn test_to_cnf_double_negation_collapses() {
+        assert_eq!(
+            to_cnf(Expr::Not {
+                operand: Box::new(Expr::Not { operand: Box::new(Expr::var("a")) })
+            }),
+            to_cnf(Expr::var("a"))
+        );
+    }
+
+    fn implies(left: Expr, right: Expr) -> Expr {
+        Expr::Impl {
+            left: Box::new(left),
+            right: Box::new(right),
+        }
+    }
+
+    #[test]
+    fn test_lj_provable_defers_left_impl_until_right_impl_is_saturated() {
+        // (P -> (P -> Q)) -> (P -> Q): needs right-> decomposed before the antecedent's
+        // P -> (P -> Q) is touched, since decomposing that left-> first would demand proving
+        // `⊢ P` on its own, which doesn't hold. Eagerly applying left-> here is exactly the bug
+        // this regression test guards against.
+        let p = Expr::var("P");
+        let q = Expr::var("Q");
+        let goal = implies(implies(p.clone(), implies(p, q.clone())), implies(Expr::var("P"), q));
+        assert!(lj_provable(vec![], vec![goal]).is_ok());
+    }
+
+    #[test]
+    fn test_lj_provable_rejects_excluded_middle() {
+        // P | ~P is classically valid but not intuitionistically provable.
+        let p = Expr::var("P");
+        let goal = Expr::or(p.clone(), Expr::not(p));
+        assert!(lj_provable(vec![], vec![goal]).is_err());
+    }
+
+    #[test]
+    fn test_lj_provable_accepts_modus_ponens() {
+        // P, P -> Q ⊢ Q holds constructively too.
+        let p = Expr::var("P");
+        let q = Expr::var("Q");
+        let antecedent = vec![p.clone(), implies(p, q.clone())];
+        assert!(lj_provable(antecedent, vec![q]).is_ok());
+    }
+
+    #[test]
+    fn test_lj_provable_backtracks_over_antecedent_implication_order() {
+        // {p->q, (p->q)->p} ⊢ p: decomposing (p->q)->p first works (its minor premise p->q is
+        // already in the antecedent), but decomposing p->q first doesn't (it demands proving
+        // `⊢ p` on its own, which fails) -- so this only holds if a failed choice backtracks to
+        // try the other implication, rather than committing to whichever was cited first.
+        let p = Expr::var("P");
+        let q = Expr::var("Q");
+        let p_implies_q = implies(p.clone(), q);
+        let antecedent = vec![p_implies_q.clone(), implies(p_implies_q, p.clone())];
+        assert!(lj_provable(antecedent.clone(), vec![p.clone()]).is_ok());
+        // Order of citation shouldn't matter either.
+        assert!(lj_provable(antecedent.into_iter().rev().collect(), vec![p]).is_ok());
+    }
+
+    #[test]
+    fn test_lj_provable_rejects_double_negation_elimination() {
+        // ~~P -> P is classically valid but not intuitionistically provable.
+        let p = Expr::var("P");
+        let goal = implies(Expr::not(Expr::not(p.clone())), p);
+        assert!(lj_provable(vec![], vec![goal]).is_err());
+    }
+
+    #[test]
+    fn test_classical_only_rules_are_exactly_those_resting_on_excluded_middle() {
+        // Every rule classified `RuleClassification::Classical`, so that `SharedChecks::check`
+        // rejects it under `LogicMode::Intuitionistic` -- and, just as important, a few
+        // neighboring rules that look similar but are constructively fine, so this doesn't just
+        // degrade into "get_classifications returns something".
+        let classical = [
+            RuleM::NotElim,
+            RuleM::DoubleNegation,
+            RuleM::ConjunctiveNormalForm,
+            RuleM::DisjunctiveNormalForm,
+            RuleM::Implication,
+            RuleM::Contraposition,
+            RuleM::ExcludedMiddle,
+            RuleM::AristoteleanSquare,
+            RuleM::QuantifierNegation,
+            RuleM::QuantifierDistribution,
+            RuleM::DeMorgan,
+        ];
+        for rule in classical {
+            assert!(
+                rule.get_classifications().contains(&RuleClassification::Classical),
+                "{} should be classified Classical",
+                rule.get_name()
+            );
+        }
+        let not_classical = [
+            RuleM::AndIntro,
+            RuleM::AndElim,
+            RuleM::ImpIntro,
+            RuleM::ImpElim,
+            RuleM::Reit,
+            RuleM::NullQuantification,
+        ];
+        for rule in not_classical {
+            assert!(
+                !rule.get_classifications().contains(&RuleClassification::Classical),
+                "{} should not be classified Classical",
+                rule.get_name()
+            );
+        }
+    }
+
+    #[test]
+    fn test_not_elim_rejected_intuitionistically_accepted_classically() {
+        use crate::parser::parse_unwrap as p;
+
+        type P = crate::proofs::pooledproof::PooledProof<Hlist![Expr]>;
+
+        let mut prf = P::new();
+        let r1 = prf.add_premise(p("~~A"));
+
+        assert!(matches!(
+            RuleM::NotElim.check(&prf, p("A"), vec![r1.clone()], vec![], LogicMode::Intuitionistic),
+            Err(ProofCheckError::NotConstructive(_))
+        ));
+        assert!(RuleM::NotElim.check(&prf, p("A"), vec![r1], vec![], LogicMode::Classical).is_ok());
+    }
+
+    #[test]
+    fn test_tautological_consequence_excluded_middle_rejected_intuitionistically_accepted_classically() {
+        use crate::parser::parse_unwrap as p;
+
+        type P = crate::proofs::pooledproof::PooledProof<Hlist![Expr]>;
+
+        let prf = P::new();
+        let conclusion = p("A | ~A");
+
+        assert!(RuleM::TautologicalConsequence.check(&prf, conclusion.clone(), vec![], vec![], LogicMode::Intuitionistic).is_err());
+        assert!(RuleM::TautologicalConsequence.check(&prf, conclusion, vec![], vec![], LogicMode::Classical).is_ok());
+    }
+
+    #[test]
+    fn test_tautological_consequence_accepts_constructively_valid_step_intuitionistically() {
+        // Modus ponens doesn't rest on excluded middle, so `TautologicalConsequence` should
+        // still accept it in `LogicMode::Intuitionistic`, unlike the rule itself being rejected
+        // outright the way `NotElim` is -- the gate here is `lj_provable`, not `SharedChecks`.
+        use crate::parser::parse_unwrap as p;
+
+        type P = crate::proofs::pooledproof::PooledProof<Hlist![Expr]>;
+
+        let mut prf = P::new();
+        let r1 = prf.add_premise(p("A"));
+        let r2 = prf.add_premise(p("A -> B"));
+
+        assert!(RuleM::TautologicalConsequence
+            .check(&prf, p("B"), vec![r1, r2], vec![], LogicMode::Intuitionistic)
+            .is_ok());
+    }
 }
@@ -0,0 +1,293 @@
+/*!
+A small self-contained DPLL SAT solver with a Tseitin CNF encoder for `expr::Expr`.
+
+Used by `ContradictionIntro`, which checks that its premises are jointly unsatisfiable by
+encoding their conjunction and showing the encoding has no model. The Tseitin transform introduces a fresh
+variable per compound subexpression (rather than distributing `∨` over `∧` directly), which
+keeps the resulting CNF linear in the size of the formula instead of exponential.
+*/
+
+use crate::expr::Expr;
+use crate::expr::Op;
+
+use std::collections::HashMap;
+
+/// A literal: a variable id, negated for the negative form. `0` is never used.
+pub type Lit = i64;
+/// A clause: a disjunction of literals.
+pub type Clause = Vec<Lit>;
+
+/// Assigns a fresh boolean variable to each distinct compound subexpression and emits clauses
+/// encoding that variable's equivalence to the subexpression's semantics.
+pub struct TseitinEncoder {
+    next_var: i64,
+    clauses: Vec<Clause>,
+    atom_vars: HashMap<String, i64>,
+    /// Variables for non-propositional subexpressions (quantifiers, predicate application, etc.)
+    /// that aren't covered by `atom_vars`, keyed on the subexpression itself so that two
+    /// syntactically identical occurrences (e.g. the same quantified formula in two different
+    /// dependencies) are hash-consed to the same variable rather than treated as unrelated atoms.
+    compound_vars: HashMap<Expr, i64>,
+}
+
+impl TseitinEncoder {
+    pub fn new() -> Self {
+        Self {
+            next_var: 1,
+            clauses: Vec::new(),
+            atom_vars: HashMap::new(),
+            compound_vars: HashMap::new(),
+        }
+    }
+
+    fn fresh(&mut self) -> i64 {
+        let v = self.next_var;
+        self.next_var += 1;
+        v
+    }
+
+    /// Encode `expr`, returning the variable whose truth value tracks `expr`'s truth value.
+    /// Non-propositional constructs (quantifiers, predicate application, etc.) are hash-consed to
+    /// an opaque atom via `compound_vars`, so callers working outside the boolean fragment get a
+    /// sound (if incomplete -- it can't see e.g. quantifier instantiation) propositional
+    /// abstraction rather than a fresh, unrelated variable per occurrence.
+    pub fn encode(&mut self, expr: &Expr) -> i64 {
+        match expr {
+            Expr::Var { name } => *self
+                .atom_vars
+                .entry(name.clone())
+                .or_insert_with(|| {
+                    self.next_var += 1;
+                    self.next_var - 1
+                }),
+            Expr::Contra => {
+                let v = self.fresh();
+                self.clauses.push(vec![-v]);
+                v
+            }
+            Expr::Not { operand } => {
+                let a = self.encode(operand);
+                let v = self.fresh();
+                // v <-> !a
+                self.clauses.push(vec![-v, -a]);
+                self.clauses.push(vec![v, a]);
+                v
+            }
+            Expr::Impl { left, right } => {
+                let a = self.encode(left);
+                let b = self.encode(right);
+                let v = self.fresh();
+                // v <-> (!a | b)
+                self.clauses.push(vec![-v, -a, b]);
+                self.clauses.push(vec![v, a]);
+                self.clauses.push(vec![v, -b]);
+                v
+            }
+            Expr::Assoc { op, exprs } => {
+                let vars: Vec<i64> = exprs.iter().map(|e| self.encode(e)).collect();
+                let v = self.fresh();
+                match op {
+                    Op::And => {
+                        let mut clause = vec![v];
+                        for &a in &vars {
+                            self.clauses.push(vec![-v, a]);
+                            clause.push(-a);
+                        }
+                        self.clauses.push(clause);
+                    }
+                    Op::Or => {
+                        let mut clause = vec![-v];
+                        for &a in &vars {
+                            self.clauses.push(vec![v, -a]);
+                            clause.push(a);
+                        }
+                        self.clauses.push(clause);
+                    }
+                    Op::Bicon | Op::Equiv => {
+                        // v <-> (a1 <-> a2 <-> ... <-> an), chained pairwise through auxiliary
+                        // "these two agree" variables, then v <-> AND(those).
+                        let mut eqs = Vec::new();
+                        for w in vars.windows(2) {
+                            let (a, b) = (w[0], w[1]);
+                            let e = self.fresh();
+                            self.clauses.push(vec![-e, -a, b]);
+                            self.clauses.push(vec![-e, a, -b]);
+                            self.clauses.push(vec![e, a, b]);
+                            self.clauses.push(vec![e, -a, -b]);
+                            eqs.push(e);
+                        }
+                        let mut clause = vec![v];
+                        for &e in &eqs {
+                            self.clauses.push(vec![-v, e]);
+                            clause.push(-e);
+                        }
+                        self.clauses.push(clause);
+                    }
+                }
+                v
+            }
+            _ => {
+                if let Some(&v) = self.compound_vars.get(expr) {
+                    v
+                } else {
+                    let v = self.fresh();
+                    self.compound_vars.insert(expr.clone(), v);
+                    v
+                }
+            }
+        }
+    }
+
+    /// Assert `lit` as a unit clause (positive to assert the variable true, negative false).
+    pub fn assert_literal(&mut self, lit: Lit) {
+        self.clauses.push(vec![lit]);
+    }
+
+    pub fn into_clauses(self) -> Vec<Clause> {
+        self.clauses
+    }
+
+    /// Map each variable id the encoder has allocated to a human-readable label: the atom's own
+    /// name, or the stringified subexpression for a hash-consed compound. Lets a caller that got
+    /// a model back from `find_model` render it as a readable counter-model instead of bare
+    /// variable ids.
+    pub fn describe_vars(&self) -> HashMap<i64, String> {
+        let mut out = HashMap::new();
+        for (name, &v) in &self.atom_vars {
+            out.insert(v, name.clone());
+        }
+        for (expr, &v) in &self.compound_vars {
+            out.insert(v, expr.to_string());
+        }
+        out
+    }
+}
+
+impl Default for TseitinEncoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Decide satisfiability of a CNF formula via DPLL: unit propagation, pure-literal
+/// elimination, and branching with backtracking when neither applies.
+pub fn is_satisfiable(clauses: &[Clause]) -> bool {
+    dpll(clauses.to_vec())
+}
+
+/// Like `is_satisfiable`, but on success returns a satisfying assignment (as the literals forced
+/// true along the way) rather than a bare boolean -- for callers that want to show a concrete
+/// counter-model rather than just reject the input. Doesn't bother with pure-literal
+/// elimination, since eliminating a variable that way would need to be undone to recover its
+/// value for the model.
+pub fn find_model(clauses: &[Clause]) -> Option<Vec<Lit>> {
+    dpll_model(clauses.to_vec(), vec![])
+}
+
+fn dpll_model(mut clauses: Vec<Clause>, mut forced: Vec<Lit>) -> Option<Vec<Lit>> {
+    loop {
+        match clauses.iter().find(|c| c.len() == 1).map(|c| c[0]) {
+            Some(lit) => {
+                forced.push(lit);
+                clauses = simplify(&clauses, lit.abs(), lit > 0)?;
+            }
+            None => break,
+        }
+    }
+    if clauses.is_empty() {
+        return Some(forced);
+    }
+    let var = clauses[0][0].abs();
+    for &val in &[true, false] {
+        let mut trial = clauses.clone();
+        trial.push(vec![if val { var } else { -var }]);
+        if let Some(model) = dpll_model(trial, forced.clone()) {
+            return Some(model);
+        }
+    }
+    None
+}
+
+fn dpll(clauses: Vec<Clause>) -> bool {
+    let clauses = match unit_propagate(clauses) {
+        Some(c) => c,
+        None => return false, // a clause reduced to empty: conflict
+    };
+    if clauses.is_empty() {
+        return true;
+    }
+    let clauses = eliminate_pure_literals(clauses);
+    if clauses.is_empty() {
+        return true;
+    }
+
+    // Branch on the first literal of the first remaining clause.
+    let var = clauses[0][0].abs();
+    for &val in &[true, false] {
+        let mut trial = clauses.clone();
+        trial.push(vec![if val { var } else { -var }]);
+        if dpll(trial) {
+            return true;
+        }
+    }
+    false
+}
+
+/// Apply the assignment `var = val` to every clause: drop clauses it satisfies, drop the
+/// falsified literal from the rest. Returns `None` on a conflict (some clause becomes empty).
+fn simplify(clauses: &[Clause], var: i64, val: bool) -> Option<Vec<Clause>> {
+    let lit = if val { var } else { -var };
+    let mut out = Vec::with_capacity(clauses.len());
+    for clause in clauses {
+        if clause.contains(&lit) {
+            continue;
+        }
+        let reduced: Clause = clause.iter().cloned().filter(|&l| l != -lit).collect();
+        if reduced.is_empty() {
+            return None;
+        }
+        out.push(reduced);
+    }
+    Some(out)
+}
+
+/// Repeatedly force the literal of any unit clause (a clause with exactly one remaining
+/// literal) until no unit clauses remain, propagating the consequences via `simplify`.
+fn unit_propagate(mut clauses: Vec<Clause>) -> Option<Vec<Clause>> {
+    loop {
+        match clauses.iter().find(|c| c.len() == 1) {
+            Some(c) => {
+                let lit = c[0];
+                clauses = simplify(&clauses, lit.abs(), lit > 0)?;
+            }
+            None => return Some(clauses),
+        }
+    }
+}
+
+/// A variable appearing with only one polarity across all clauses can be assigned to satisfy
+/// every clause it appears in; this removes it (and those clauses) from further search.
+fn eliminate_pure_literals(clauses: Vec<Clause>) -> Vec<Clause> {
+    let mut polarity: HashMap<i64, Option<bool>> = HashMap::new();
+    for clause in &clauses {
+        for &lit in clause {
+            let var = lit.abs();
+            let sign = lit > 0;
+            polarity
+                .entry(var)
+                .and_modify(|p| {
+                    if *p != Some(sign) {
+                        *p = None;
+                    }
+                })
+                .or_insert(Some(sign));
+        }
+    }
+    let mut result = clauses;
+    for (var, sign) in polarity.into_iter().filter_map(|(v, p)| p.map(|s| (v, s))) {
+        if let Some(simplified) = simplify(&result, var, sign) {
+            result = simplified;
+        }
+    }
+    result
+}
@@ -0,0 +1,222 @@
+/*!
+SMT-LIB2 serialization and an external-solver-backed justification rule.
+
+Unlike the other `AutomationRelatedRules`, `Prover` doesn't decide anything itself: it
+serializes `deps` and the negation of `conclusion` to SMT-LIB2 and asks a configured external
+solver binary (e.g. z3, cvc5) whether that conjunction is unsatisfiable. This reaches outside the
+propositional fragment `sat::TseitinEncoder` covers -- predicate applications become
+uninterpreted functions and quantifiers become real `forall`/`exists` binders, rather than opaque
+atoms -- at the cost of depending on an external process. Gated behind the `smt` cargo feature
+and a configurable solver path, so a build with neither still compiles and every other rule still
+runs; `Prover` just isn't available.
+*/
+
+#![cfg(feature = "smt")]
+
+use crate::congruence;
+use crate::expr::Expr;
+use crate::expr::Op;
+use crate::expr::QuantKind;
+
+use std::collections::BTreeSet;
+use std::io::Write;
+use std::process::Command;
+use std::process::Stdio;
+
+/// How to invoke the external solver. Defaults to `z3 -in` (read a script on stdin), overridable
+/// via the `ARIS_SMT_SOLVER` environment variable as a whitespace-separated command line, so a
+/// deployment without `z3` on `PATH` can point at cvc5 or a wrapper script instead.
+pub struct SolverConfig {
+    pub command: Vec<String>,
+}
+
+impl SolverConfig {
+    pub fn from_env() -> Self {
+        let command = std::env::var("ARIS_SMT_SOLVER")
+            .unwrap_or_else(|_| "z3 -in".to_string())
+            .split_whitespace()
+            .map(str::to_string)
+            .collect();
+        SolverConfig { command }
+    }
+}
+
+/// The solver's verdict on `(and deps... (not conclusion))`.
+pub enum ProverResult {
+    /// Unsatisfiable: `conclusion` is entailed by `deps`.
+    Unsat,
+    /// Satisfiable, with the solver's model rendered as text if it reported one.
+    Sat(Option<String>),
+    /// The solver gave up.
+    Unknown,
+}
+
+#[derive(Debug)]
+pub enum SmtError {
+    Io(std::io::Error),
+    UnexpectedOutput(String),
+}
+
+impl std::fmt::Display for SmtError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            SmtError::Io(e) => write!(f, "couldn't run the external solver: {}", e),
+            SmtError::UnexpectedOutput(s) => write!(f, "unexpected solver output: {}", s),
+        }
+    }
+}
+
+impl From<std::io::Error> for SmtError {
+    fn from(e: std::io::Error) -> Self {
+        SmtError::Io(e)
+    }
+}
+
+/// A declared SMT-LIB symbol: `name`, applied to `arity` arguments, producing `Bool` if
+/// `result_bool` (a predicate) or the uninterpreted sort `Term` otherwise (a function/constant).
+/// This Expr model has no sort annotations, so whether a symbol is a predicate or a term-valued
+/// function is inferred from the syntactic position it's used in (see `to_term`'s
+/// `bool_position` argument).
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+struct Decl {
+    name: String,
+    arity: usize,
+    result_bool: bool,
+}
+
+/// Walk `expr`, emitting its SMT-LIB2 term and recording every free symbol it mentions (function
+/// symbol and arity, plus whether it's used in boolean or term position) into `decls`.
+/// `bound` holds the names of enclosing quantifier binders, which get no declaration since
+/// they're already in scope as SMT-LIB bound variables. `bool_position` is the sort expected of
+/// `expr` itself: `true` for a formula, `false` for a term, and determines how a bare `Var` or an
+/// application's head symbol is declared.
+fn to_term(expr: &Expr, bound: &BTreeSet<String>, bool_position: bool, decls: &mut BTreeSet<Decl>) -> String {
+    match expr {
+        Expr::Contra => "false".to_string(),
+        Expr::Var { name } => {
+            if let Some((head, args)) = congruence::parse_application(expr) {
+                decls.insert(Decl { name: head.clone(), arity: args.len(), result_bool: bool_position });
+                if args.is_empty() {
+                    head
+                } else {
+                    let arg_terms = args
+                        .iter()
+                        .map(|a| to_term(a, bound, false, decls))
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    format!("({} {})", head, arg_terms)
+                }
+            } else if bound.contains(name) {
+                name.clone()
+            } else {
+                decls.insert(Decl { name: name.clone(), arity: 0, result_bool: bool_position });
+                name.clone()
+            }
+        }
+        Expr::Not { operand } => format!("(not {})", to_term(operand, bound, true, decls)),
+        Expr::Impl { left, right } => format!(
+            "(=> {} {})",
+            to_term(left, bound, true, decls),
+            to_term(right, bound, true, decls)
+        ),
+        Expr::Assoc { op, exprs } => {
+            // And/Or operands are always formulas; Bicon/Equiv's operands inherit this
+            // expression's own position, since this Expr model overloads `Equiv` for both
+            // logical equivalence (formula operands) and term equality (term operands).
+            let operand_bool_position = match op {
+                Op::And | Op::Or => true,
+                Op::Bicon | Op::Equiv => bool_position,
+            };
+            let terms = exprs
+                .iter()
+                .map(|e| to_term(e, bound, operand_bool_position, decls))
+                .collect::<Vec<_>>();
+            match op {
+                Op::And => format!("(and {})", terms.join(" ")),
+                Op::Or => format!("(or {})", terms.join(" ")),
+                Op::Bicon | Op::Equiv => format!("(= {})", terms.join(" ")),
+            }
+        }
+        Expr::Quant { kind, name, body } => {
+            let keyword = match kind {
+                QuantKind::Forall => "forall",
+                QuantKind::Exists => "exists",
+            };
+            let mut inner_bound = bound.clone();
+            inner_bound.insert(name.clone());
+            format!(
+                "({} (({} Term)) {})",
+                keyword,
+                name,
+                to_term(body, &inner_bound, true, decls)
+            )
+        }
+    }
+}
+
+/// Render `decls` as `declare-fun`/`declare-const` commands, one per line.
+fn render_decls(decls: &BTreeSet<Decl>) -> String {
+    decls
+        .iter()
+        .map(|d| {
+            let sort = if d.result_bool { "Bool" } else { "Term" };
+            if d.arity == 0 {
+                format!("(declare-const {} {})", d.name, sort)
+            } else {
+                let arg_sorts = std::iter::repeat("Term").take(d.arity).collect::<Vec<_>>().join(" ");
+                format!("(declare-fun {} ({}) {})", d.name, arg_sorts, sort)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Build the SMT-LIB2 script asking whether `deps` entail `conclusion`: declares `Term` as an
+/// uninterpreted sort, declares every free symbol `to_term` encounters, asserts each dependency,
+/// asserts the negation of `conclusion`, and checks satisfiability of the result. `deps` entails
+/// `conclusion` exactly when this script is `unsat`.
+fn build_query(deps: &[Expr], conclusion: &Expr) -> String {
+    let mut decls = BTreeSet::new();
+    let bound = BTreeSet::new();
+    let dep_terms: Vec<String> = deps.iter().map(|d| to_term(d, &bound, true, &mut decls)).collect();
+    let conclusion_term = to_term(conclusion, &bound, true, &mut decls);
+
+    let mut script = String::new();
+    script.push_str("(declare-sort Term 0)\n");
+    script.push_str(&render_decls(&decls));
+    script.push('\n');
+    for term in &dep_terms {
+        script.push_str(&format!("(assert {})\n", term));
+    }
+    script.push_str(&format!("(assert (not {}))\n", conclusion_term));
+    script.push_str("(check-sat)\n(get-model)\n");
+    script
+}
+
+/// Ask the solver named by `config` whether `deps` entails `conclusion`, by spawning it over
+/// stdin/stdout and feeding it the query built by `build_query`.
+pub fn discharge(deps: &[Expr], conclusion: &Expr, config: &SolverConfig) -> Result<ProverResult, SmtError> {
+    let query = build_query(deps, conclusion);
+    let (program, args) = config.command.split_first().ok_or_else(|| {
+        SmtError::UnexpectedOutput("ARIS_SMT_SOLVER names no executable".to_string())
+    })?;
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    child.stdin.take().expect("piped stdin").write_all(query.as_bytes())?;
+    let output = child.wait_with_output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut lines = stdout.lines();
+    match lines.next().map(str::trim) {
+        Some("unsat") => Ok(ProverResult::Unsat),
+        Some("sat") => {
+            let model: String = lines.collect::<Vec<_>>().join("\n");
+            Ok(ProverResult::Sat(if model.trim().is_empty() { None } else { Some(model) }))
+        }
+        Some("unknown") => Ok(ProverResult::Unknown),
+        _ => Err(SmtError::UnexpectedOutput(stdout.into_owned())),
+    }
+}
@@ -0,0 +1,106 @@
+/*!
+A signed-formula tableau prover for propositional validity, producing a checkable proof object
+instead of a bare yes/no.
+
+Used by `TautologicalConsequence`. A signed formula `(e, true)` reads as "`e`, on the right of the
+sequent" (something to prove); `(e, false)` reads as "`e`, on the left" (something assumed). This
+is the same propositional decomposition `lk_provable` performs for `Tautology` (antecedent/negative
+on one side, succedent/positive on the other), just carried around as a single tagged list rather
+than two, and with every step recorded into a `TableauProof` rather than discarded -- so a branch
+that closes leaves behind the derivation that closed it, and a branch that doesn't leaves behind
+the falsifying assignment.
+*/
+
+use crate::expr::Expr;
+use crate::expr::Op;
+
+/// A formula together with which side of the sequent it's asserted on: `true` for the right
+/// (succedent, to prove), `false` for the left (antecedent, assumed).
+pub type SignedFormula = (Expr, bool);
+
+/// A node of a closed tableau, witnessing that the branch it was built from is unsatisfiable.
+#[derive(Debug, Clone)]
+pub enum TableauProof {
+    /// The branch closed here because `Expr` occurs on the branch with both polarities.
+    Closed(Expr),
+    /// A non-branching step: decomposing `formula` extends the same branch with its signed
+    /// subformulas (`Not`, positive `Or`/`Impl`, negative `And`).
+    Alpha(SignedFormula, Box<TableauProof>),
+    /// A branching step: decomposing `formula` splits the branch into several, one per resulting
+    /// subformula (positive `And`, negative `Or`/`Impl`), every one of which must close.
+    Beta(SignedFormula, Vec<TableauProof>),
+}
+
+/// True iff `e` isn't decomposed further by `tableau_prove` -- a leaf as far as this calculus is
+/// concerned (an atom, `Contra`, a quantified formula, or a biconditional/equality).
+fn is_atomic(e: &Expr) -> bool {
+    !matches!(
+        e,
+        Expr::Not { .. } | Expr::Impl { .. } | Expr::Assoc { op: Op::And | Op::Or, .. }
+    )
+}
+
+/// Attempt to close the tableau branch `signed`, returning the proof on success or the branch's
+/// final, fully-decomposed (all-atomic) state on failure -- an assignment of truth values (`false`
+/// for a positive atom, `true` for a negative one) that falsifies the step.
+pub fn tableau_prove(signed: Vec<SignedFormula>) -> Result<TableauProof, Vec<SignedFormula>> {
+    if let Some((atom, _)) = signed.iter().find(|(e, pol)| signed.contains(&(e.clone(), !pol))) {
+        return Ok(TableauProof::Closed(atom.clone()));
+    }
+    let Some(i) = signed.iter().position(|(e, _)| !is_atomic(e)) else {
+        return Err(signed);
+    };
+    let (formula, polarity) = signed[i].clone();
+    let mut rest = signed;
+    rest.remove(i);
+    match (&formula, polarity) {
+        // Not flips polarity onto its operand, in a single branch.
+        (Expr::Not { operand }, pol) => {
+            rest.push(((**operand).clone(), !pol));
+            tableau_prove(rest).map(|t| TableauProof::Alpha((formula, polarity), Box::new(t)))
+        }
+        // alpha: positive Or and negative And extend the same branch with every subformula.
+        (Expr::Assoc { op: Op::Or, exprs }, true) | (Expr::Assoc { op: Op::And, exprs }, false) => {
+            rest.extend(exprs.iter().cloned().map(|e| (e, polarity)));
+            tableau_prove(rest).map(|t| TableauProof::Alpha((formula, polarity), Box::new(t)))
+        }
+        // alpha: positive Impl extends the same branch with its antecedent negated and its
+        // consequent positive.
+        (Expr::Impl { left, right }, true) => {
+            rest.push(((**left).clone(), false));
+            rest.push(((**right).clone(), true));
+            tableau_prove(rest).map(|t| TableauProof::Alpha((formula, polarity), Box::new(t)))
+        }
+        // beta: positive And and negative Or split into one branch per subformula.
+        (Expr::Assoc { op: Op::And, exprs }, true) | (Expr::Assoc { op: Op::Or, exprs }, false) => {
+            let mut branches = Vec::with_capacity(exprs.len());
+            for e in exprs {
+                let mut branch = rest.clone();
+                branch.push((e.clone(), polarity));
+                branches.push(tableau_prove(branch)?);
+            }
+            Ok(TableauProof::Beta((formula, polarity), branches))
+        }
+        // beta: negative Impl splits into "its antecedent positive" and "its consequent negative".
+        (Expr::Impl { left, right }, false) => {
+            let mut left_branch = rest.clone();
+            left_branch.push(((**left).clone(), true));
+            let mut right_branch = rest;
+            right_branch.push(((**right).clone(), false));
+            let branches = vec![tableau_prove(left_branch)?, tableau_prove(right_branch)?];
+            Ok(TableauProof::Beta((formula, polarity), branches))
+        }
+        _ => unreachable!("is_atomic said {} wasn't atomic", formula),
+    }
+}
+
+/// Render an open branch (the `Err` side of `tableau_prove`) as a falsifying assignment: every
+/// left (negative/assumed) formula true, every right (positive/to-prove) formula false -- the
+/// same reading `lk_provable`'s leaf sequents get.
+pub fn pretty_open_branch(branch: &[SignedFormula]) -> String {
+    branch
+        .iter()
+        .map(|(e, pol)| format!("{} = {}", e, !pol))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
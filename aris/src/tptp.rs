@@ -0,0 +1,193 @@
+/*!
+TPTP FOF export and an external-ATP-backed first-order fallback for `TautologicalConsequence`.
+
+`tableau::tableau_prove` (the propositional decision procedure `TautologicalConsequence` uses by
+default) treats quantified formulas and predicate applications as opaque atoms -- sound, but
+incomplete: it can reject an argument that's genuinely first-order valid just because its validity
+depends on quantifier reasoning the tableau can't see. This module is the analogue of `smt.rs` for
+that gap: instead of calling out to an SMT solver over uninterpreted functions, it renders the
+problem as TPTP FOF syntax and asks a general-purpose first-order ATP (E, Vampire, ...) whether the
+premises entail the conclusion, reading the verdict off the prover's `SZS status` line. Gated
+behind the `tptp` cargo feature and a configurable prover command, same as `smt.rs`, so a build with
+neither still compiles and the propositional fast path still works.
+*/
+
+#![cfg(feature = "tptp")]
+
+use crate::congruence;
+use crate::expr::Expr;
+use crate::expr::Op;
+use crate::expr::QuantKind;
+
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::process::Command;
+use std::process::Stdio;
+
+/// How to invoke the external prover. Defaults to a schedule of E's strategies, overridable via
+/// the `ARIS_TPTP_PROVER` environment variable as a whitespace-separated command line, so a
+/// deployment without `eprover` on `PATH` can point at Vampire or a wrapper script instead.
+pub struct SolverConfig {
+    pub command: Vec<String>,
+}
+
+impl SolverConfig {
+    pub fn from_env() -> Self {
+        let command = std::env::var("ARIS_TPTP_PROVER")
+            .unwrap_or_else(|_| "eprover --auto-schedule -s".to_string())
+            .split_whitespace()
+            .map(str::to_string)
+            .collect();
+        SolverConfig { command }
+    }
+}
+
+/// The prover's verdict on whether the premises entail the conclusion.
+pub enum ProverResult {
+    /// `SZS status Theorem` or `Unsatisfiable`: the conclusion follows from the premises.
+    Unsat,
+    /// `SZS status CounterSatisfiable` or `Satisfiable`: the premises don't entail the conclusion.
+    Sat,
+    /// The prover gave up (timeout, resource-out, or any other non-refutational status).
+    Unknown,
+}
+
+#[derive(Debug)]
+pub enum TptpError {
+    Io(std::io::Error),
+    NoSzsStatus(String),
+}
+
+impl std::fmt::Display for TptpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            TptpError::Io(e) => write!(f, "couldn't run the external prover: {}", e),
+            TptpError::NoSzsStatus(s) => write!(f, "prover output had no SZS status line: {}", s),
+        }
+    }
+}
+
+impl From<std::io::Error> for TptpError {
+    fn from(e: std::io::Error) -> Self {
+        TptpError::Io(e)
+    }
+}
+
+/// True iff `expr` mentions a quantifier or a predicate/function application (as parsed by
+/// `congruence::parse_application`) anywhere in its structure -- i.e. the part of the first-order
+/// fragment the propositional tableau can't see through. `TautologicalConsequence` only pays for
+/// an ATP dispatch when this holds for some premise or the conclusion.
+pub fn needs_first_order(expr: &Expr) -> bool {
+    match expr {
+        Expr::Contra => false,
+        Expr::Var { .. } => congruence::parse_application(expr).is_some(),
+        Expr::Not { operand } => needs_first_order(operand),
+        Expr::Impl { left, right } => needs_first_order(left) || needs_first_order(right),
+        Expr::Assoc { exprs, .. } => exprs.iter().any(needs_first_order),
+        Expr::Quant { .. } => true,
+    }
+}
+
+/// Render `expr` as a TPTP FOF term/formula. `bound` maps this formula's enclosing quantifier
+/// binders to the (capitalized, as TPTP variables must be) name they were given in the output;
+/// free `Expr::Var`s and application heads are emitted as TPTP constants/functors, as-is when they
+/// already start with a lowercase letter and with a `c_` prefix otherwise. `bool_position` is
+/// `true` when `expr` is itself a formula (as opposed to a term), and disambiguates `Equiv`
+/// between logical `<=>` (formula operands) and term equality `=` (term operands), the same
+/// ambiguity `smt.rs::to_term` resolves the same way.
+fn to_fof(expr: &Expr, bound: &BTreeMap<String, String>, bool_position: bool) -> String {
+    match expr {
+        Expr::Contra => "$false".to_string(),
+        Expr::Var { name } => {
+            // TPTP reads a name that doesn't start with a lowercase letter as a variable.
+            let functor = |s: &str| {
+                if s.starts_with(|c: char| c.is_ascii_lowercase()) { s.to_string() } else { format!("c_{}", s) }
+            };
+            if let Some((head, args)) = congruence::parse_application(expr) {
+                let arg_terms = args
+                    .iter()
+                    .map(|a| to_fof(a, bound, false))
+                    .collect::<Vec<_>>()
+                    .join(",");
+                format!("{}({})", functor(&head), arg_terms)
+            } else if let Some(var) = bound.get(name) {
+                var.clone()
+            } else {
+                functor(name)
+            }
+        }
+        Expr::Not { operand } => format!("~({})", to_fof(operand, bound, true)),
+        Expr::Impl { left, right } => {
+            format!("({} => {})", to_fof(left, bound, true), to_fof(right, bound, true))
+        }
+        Expr::Assoc { op, exprs } => {
+            let operand_bool_position = match op {
+                Op::And | Op::Or => true,
+                Op::Bicon | Op::Equiv => bool_position,
+            };
+            let terms = exprs.iter().map(|e| to_fof(e, bound, operand_bool_position)).collect::<Vec<_>>();
+            match op {
+                Op::And => format!("({})", terms.join(" & ")),
+                Op::Or => format!("({})", terms.join(" | ")),
+                Op::Bicon if bool_position => format!("({})", terms.join(" <=> ")),
+                Op::Bicon => format!("({})", terms.join(" = ")),
+                Op::Equiv if bool_position => format!("({})", terms.join(" <=> ")),
+                Op::Equiv => format!("({})", terms.join(" = ")),
+            }
+        }
+        Expr::Quant { kind, name, body } => {
+            let tptp_var = format!("V{}", name);
+            let mut inner_bound = bound.clone();
+            inner_bound.insert(name.clone(), tptp_var.clone());
+            let quantifier = match kind {
+                QuantKind::Forall => "!",
+                QuantKind::Exists => "?",
+            };
+            format!("{} [{}] : ({})", quantifier, tptp_var, to_fof(body, &inner_bound, true))
+        }
+    }
+}
+
+/// Build the TPTP FOF problem asking whether `deps` entail `conclusion`: each dependency becomes
+/// a numbered `axiom`, and `conclusion` becomes the `conjecture` -- the idiomatic TPTP way to pose
+/// an entailment question, letting the prover's own refutation search handle negating it.
+fn build_problem(deps: &[Expr], conclusion: &Expr) -> String {
+    let bound = BTreeMap::new();
+    let mut problem = String::new();
+    for (i, dep) in deps.iter().enumerate() {
+        problem.push_str(&format!("fof(premise_{}, axiom, {}).\n", i, to_fof(dep, &bound, true)));
+    }
+    problem.push_str(&format!("fof(goal, conjecture, {}).\n", to_fof(conclusion, &bound, true)));
+    problem
+}
+
+/// Ask the prover named by `config` whether `deps` entails `conclusion`, by spawning it over
+/// stdin/stdout, feeding it the TPTP problem built by `build_problem`, and reading its verdict off
+/// the first `SZS status` line in the output.
+pub fn discharge(deps: &[Expr], conclusion: &Expr, config: &SolverConfig) -> Result<ProverResult, TptpError> {
+    let problem = build_problem(deps, conclusion);
+    let (program, args) = config
+        .command
+        .split_first()
+        .ok_or_else(|| TptpError::NoSzsStatus("ARIS_TPTP_PROVER names no executable".to_string()))?;
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    child.stdin.take().expect("piped stdin").write_all(problem.as_bytes())?;
+    let output = child.wait_with_output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let status_line = stdout
+        .lines()
+        .find(|line| line.contains("SZS status"))
+        .ok_or_else(|| TptpError::NoSzsStatus(stdout.clone().into_owned()))?;
+    if status_line.contains("Theorem") || status_line.contains("Unsatisfiable") {
+        Ok(ProverResult::Unsat)
+    } else if status_line.contains("CounterSatisfiable") || status_line.contains("Satisfiable") {
+        Ok(ProverResult::Sat)
+    } else {
+        Ok(ProverResult::Unknown)
+    }
+}
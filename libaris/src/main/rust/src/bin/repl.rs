@@ -0,0 +1,208 @@
+//! An interactive, line-buffered REPL for building and checking a proof by hand from a terminal,
+//! without going through the web UI.
+//!
+//! Commands (one per logical entry -- see `read_command` for how an entry can still span several
+//! physical lines):
+//!   premise <expr>
+//!   step <expr> by <rule> [from <line>[,<line>...]]
+//!   subproof
+//!   end
+//!   show
+//!   undo
+//!   quit
+//!
+//! Every `<expr>` is parsed with `aris::parser::main` against the default `OperatorTable`. Lines
+//! referenced by `from` are the 1-based numbers `show` prints, spanning both whatever's already
+//! been closed off into the outermost proof and any subproof scopes still open above it --
+//! `PooledProof` hands out reference ids from one flat pool regardless of nesting, so a reference
+//! minted inside an open scope already cites correctly before that scope is attached with `end`.
+use aris::expression::Expr;
+use aris::parser::{self, OperatorTable};
+use aris::pretty::PrettyMode;
+use aris::proofs::pooledproof::PooledProof;
+use aris::proofs::{Justification, PJRef, Proof, Rule};
+
+use std::io::{self, BufRead, Write};
+
+use frunk::Coproduct;
+use frunk::Coproduct::{Inl, Inr};
+use frunk::Hlist;
+
+type P = PooledProof<Hlist![Expr]>;
+
+/// Read one logical command from `stdin`: accumulate physical lines until parentheses balance
+/// (so a long expression can be split across `Enter` keypresses) and the buffer is non-blank.
+/// Returns `Ok(None)` at EOF with nothing pending.
+fn read_command<R: BufRead>(stdin: &mut R) -> io::Result<Option<String>> {
+    let mut buffer = String::new();
+    loop {
+        print!("{}", if buffer.is_empty() { "aris> " } else { "...   " });
+        io::stdout().flush()?;
+        let mut line = String::new();
+        if stdin.read_line(&mut line)? == 0 {
+            return Ok(if buffer.trim().is_empty() { None } else { Some(buffer) });
+        }
+        buffer.push_str(&line);
+        let depth = buffer.matches('(').count() as i64 - buffer.matches(')').count() as i64;
+        if depth <= 0 && !buffer.trim().is_empty() {
+            return Ok(Some(buffer));
+        }
+    }
+}
+
+/// Only the rules this tree has an established call site for (see `proofs::proof_tests`); add an
+/// arm here as more of `Rule`'s vocabulary gets exercised elsewhere.
+fn parse_rule(name: &str) -> Option<Rule> {
+    match name {
+        "AndIntro" => Some(Rule::AndIntro),
+        "AndElim" => Some(Rule::AndElim),
+        "ImpIntro" => Some(Rule::ImpIntro),
+        "Reit" => Some(Rule::Reit),
+        _ => None,
+    }
+}
+
+/// Split `step`'s argument into `(expr source, rule name, from-clause source)`; the from-clause is
+/// `""` if the command had no `from`.
+fn parse_step(rest: &str) -> Option<(String, String, String)> {
+    let by_idx = rest.find(" by ")?;
+    let (expr_part, after_by) = rest.split_at(by_idx);
+    let after_by = &after_by[" by ".len()..];
+    match after_by.find(" from ") {
+        Some(from_idx) => {
+            let (rule_part, after_from) = after_by.split_at(from_idx);
+            let lines_part = &after_from[" from ".len()..];
+            Some((expr_part.trim().to_string(), rule_part.trim().to_string(), lines_part.trim().to_string()))
+        }
+        None => Some((expr_part.trim().to_string(), after_by.trim().to_string(), String::new())),
+    }
+}
+
+/// Every addressable line of `prf`, depth-first, alongside how deeply nested it is.
+fn index_frame(prf: &P, depth: usize, level: usize, out: &mut Vec<(usize, usize, PJRef<P>)>) {
+    for prem in prf.premises() {
+        out.push((depth, level, Coproduct::inject(prem.clone())));
+    }
+    for lineref in prf.lines() {
+        match lineref {
+            Inl(r) => out.push((depth, level, Coproduct::inject(r))),
+            Inr(Inl(sr)) => {
+                if let Some(sub) = prf.lookup_subproof(&sr) {
+                    index_frame(&sub, depth + 1, level, out);
+                }
+                // The subproof itself is also addressable, e.g. as the dependency an `ImpIntro`
+                // cites -- without this, nothing ever lands in `out` for it, and no `from`
+                // clause can ever select it.
+                out.push((depth, level, Coproduct::inject(sr)));
+            }
+            Inr(Inr(void)) => match void {},
+        }
+    }
+}
+
+/// Every line `show`/`step ... from ...` can address right now: `stack[0]`'s closed content, then
+/// each still-open scope on top of it (`stack[1..]`), each indexed against itself since it hasn't
+/// been attached to its parent with `end` yet.
+fn build_index(stack: &[P]) -> Vec<(usize, usize, PJRef<P>)> {
+    let mut out = Vec::new();
+    for (level, prf) in stack.iter().enumerate() {
+        index_frame(prf, level, level, &mut out);
+    }
+    out
+}
+
+fn describe(prf: &P, r: &PJRef<P>) -> String {
+    match prf.lookup(r.clone()) {
+        Some(Inl(e)) => format!("premise   {}", e.to_string_with(PrettyMode::Ascii)),
+        Some(Inr(Inl(Justification(e, rule, _deps)))) => format!("{} (by {:?})", e.to_string_with(PrettyMode::Ascii), rule),
+        Some(Inr(Inr(Inl(_sub)))) => "<subproof>".to_string(),
+        Some(Inr(Inr(Inr(void)))) => match void {},
+        None => "<missing>".to_string(),
+    }
+}
+
+fn print_proof(stack: &[P]) {
+    for (i, (depth, level, r)) in build_index(stack).iter().enumerate() {
+        println!("{:>3} {}{}", i + 1, "  ".repeat(*depth), describe(&stack[*level], r));
+    }
+}
+
+fn main() -> io::Result<()> {
+    let stdin = io::stdin();
+    let mut stdin = stdin.lock();
+    let table = OperatorTable::new();
+    let mut stack: Vec<P> = vec![P::new()];
+    let mut history: Vec<Vec<P>> = Vec::new();
+
+    println!("aris proof REPL -- premise / step / subproof / end / show / undo / quit");
+
+    while let Some(command) = read_command(&mut stdin)? {
+        let command = command.trim();
+        let mut words = command.splitn(2, char::is_whitespace);
+        let verb = words.next().unwrap_or("");
+        let rest = words.next().unwrap_or("").trim();
+        match verb {
+            "premise" => match parser::main(&format!("{}\n", rest), &table) {
+                Ok((_, e)) => {
+                    history.push(stack.clone());
+                    stack.last_mut().unwrap().add_premise(e);
+                }
+                Err(e) => println!("parse error: {:?}", e),
+            },
+            "step" => match parse_step(rest) {
+                None => println!("usage: step <expr> by <rule> [from <lines>]"),
+                Some((expr_src, rule_name, lines_src)) => {
+                    let parsed_expr = parser::main(&format!("{}\n", expr_src), &table);
+                    let rule = parse_rule(&rule_name);
+                    match (parsed_expr, rule) {
+                        (Err(e), _) => println!("parse error: {:?}", e),
+                        (_, None) => println!("unknown rule: {}", rule_name),
+                        (Ok((_, e)), Some(rule)) => {
+                            let index = build_index(&stack);
+                            let mut deps = Vec::new();
+                            let mut ok = true;
+                            if !lines_src.is_empty() {
+                                for piece in lines_src.split(',') {
+                                    let piece = piece.trim();
+                                    match piece.parse::<usize>().ok().and_then(|n| n.checked_sub(1)).and_then(|i| index.get(i)) {
+                                        Some((_, _, r)) => deps.push(r.clone()),
+                                        None => {
+                                            println!("no such line: {}", piece);
+                                            ok = false;
+                                        }
+                                    }
+                                }
+                            }
+                            if ok {
+                                history.push(stack.clone());
+                                stack.last_mut().unwrap().add_step(Justification(e, rule, deps));
+                            }
+                        }
+                    }
+                }
+            },
+            "subproof" => {
+                history.push(stack.clone());
+                stack.push(P::new());
+            }
+            "end" => {
+                if stack.len() < 2 {
+                    println!("no open subproof to end");
+                } else {
+                    history.push(stack.clone());
+                    let sub = stack.pop().unwrap();
+                    stack.last_mut().unwrap().add_subproof(sub);
+                }
+            }
+            "undo" => match history.pop() {
+                Some(prev) => stack = prev,
+                None => println!("nothing to undo"),
+            },
+            "show" => print_proof(&stack),
+            "quit" | "exit" => break,
+            "" => {}
+            other => println!("unknown command: {}", other),
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,212 @@
+//! A reusable traversal layer over `Expr`, so quantifier rules don't each hand-roll their own
+//! recursion to answer "what varies, and what do I replace it with".
+//!
+//! `Fold` is a visitor/fold over `Expr`'s six constructors (`Predicate`, `Unop`, `Binop`,
+//! `AssocBinop`, `Quantifier`, `Bottom`): `fold` recurses into an expression's children first,
+//! then hands the already-folded children to the matching `fold_*` method to rebuild the node,
+//! so an implementor only needs to override the constructors it actually cares about and gets
+//! identity behavior (via the provided defaults) everywhere else. `map` is built on top of it for
+//! the common case of "rewrite every node"; `fold_over` covers "accumulate something over every
+//! node" with its own post-order walk rather than through `Fold`.
+use super::{ASymbol, BSymbol, Expr, QSymbol, USymbol};
+
+use std::collections::HashSet;
+
+/// A visitor/fold over `Expr`. The default `fold_*` methods just rebuild the node from its
+/// (already-recursed) children, i.e. the identity transform; override the ones a particular
+/// traversal needs to change, and inherit the rest.
+pub trait Fold {
+    fn fold_bottom(&mut self) -> Expr {
+        Expr::Bottom
+    }
+    fn fold_predicate(&mut self, name: String, args: Vec<String>) -> Expr {
+        Expr::Predicate { name, args }
+    }
+    fn fold_unop(&mut self, symbol: USymbol, operand: Expr) -> Expr {
+        Expr::Unop { symbol, operand: Box::new(operand) }
+    }
+    fn fold_binop(&mut self, symbol: BSymbol, left: Expr, right: Expr) -> Expr {
+        Expr::Binop { symbol, left: Box::new(left), right: Box::new(right) }
+    }
+    fn fold_assocbinop(&mut self, symbol: ASymbol, exprs: Vec<Expr>) -> Expr {
+        Expr::AssocBinop { symbol, exprs }
+    }
+    fn fold_quantifier(&mut self, symbol: QSymbol, name: String, body: Expr) -> Expr {
+        Expr::Quantifier { symbol, name, body: Box::new(body) }
+    }
+
+    /// Recurse into `e`'s children (if any), then rebuild via the matching `fold_*` method.
+    fn fold(&mut self, e: Expr) -> Expr {
+        match e {
+            Expr::Bottom => self.fold_bottom(),
+            Expr::Predicate { name, args } => self.fold_predicate(name, args),
+            Expr::Unop { symbol, operand } => {
+                let operand = self.fold(*operand);
+                self.fold_unop(symbol, operand)
+            }
+            Expr::Binop { symbol, left, right } => {
+                let left = self.fold(*left);
+                let right = self.fold(*right);
+                self.fold_binop(symbol, left, right)
+            }
+            Expr::AssocBinop { symbol, exprs } => {
+                let exprs = exprs.into_iter().map(|e| self.fold(e)).collect();
+                self.fold_assocbinop(symbol, exprs)
+            }
+            Expr::Quantifier { symbol, name, body } => {
+                let body = self.fold(*body);
+                self.fold_quantifier(symbol, name, body)
+            }
+        }
+    }
+}
+
+/// `map`'s `Fold` impl: rebuilds every node exactly as the defaults would, then runs `f` over the
+/// freshly-rebuilt node on the way back up.
+struct MapEvery<'a, F>(&'a mut F);
+
+impl<'a, F: FnMut(Expr) -> Expr> Fold for MapEvery<'a, F> {
+    fn fold_bottom(&mut self) -> Expr {
+        (self.0)(Expr::Bottom)
+    }
+    fn fold_predicate(&mut self, name: String, args: Vec<String>) -> Expr {
+        (self.0)(Expr::Predicate { name, args })
+    }
+    fn fold_unop(&mut self, symbol: USymbol, operand: Expr) -> Expr {
+        (self.0)(Expr::Unop { symbol, operand: Box::new(operand) })
+    }
+    fn fold_binop(&mut self, symbol: BSymbol, left: Expr, right: Expr) -> Expr {
+        (self.0)(Expr::Binop { symbol, left: Box::new(left), right: Box::new(right) })
+    }
+    fn fold_assocbinop(&mut self, symbol: ASymbol, exprs: Vec<Expr>) -> Expr {
+        (self.0)(Expr::AssocBinop { symbol, exprs })
+    }
+    fn fold_quantifier(&mut self, symbol: QSymbol, name: String, body: Expr) -> Expr {
+        (self.0)(Expr::Quantifier { symbol, name, body: Box::new(body) })
+    }
+}
+
+/// Rewrite every subexpression of `e` (post-order, leaves first) by `f`.
+pub fn map(e: Expr, f: &mut impl FnMut(Expr) -> Expr) -> Expr {
+    MapEvery(f).fold(e)
+}
+
+/// Thread `acc` through every subexpression of `e` in post-order, the same shape as
+/// `Iterator::fold` but over `Expr`'s tree instead of a flat sequence.
+pub fn fold_over<A>(e: &Expr, acc: A, f: &mut impl FnMut(A, &Expr) -> A) -> A {
+    let acc = match e {
+        Expr::Bottom | Expr::Predicate { .. } => acc,
+        Expr::Unop { operand, .. } => fold_over(operand, acc, f),
+        Expr::Binop { left, right, .. } => {
+            let acc = fold_over(left, acc, f);
+            fold_over(right, acc, f)
+        }
+        Expr::AssocBinop { exprs, .. } => exprs.iter().fold(acc, |acc, e| fold_over(e, acc, f)),
+        Expr::Quantifier { body, .. } => fold_over(body, acc, f),
+    };
+    f(acc, e)
+}
+
+/// Every variable name that occurs free in `e`. `Predicate`'s `args` are the only place a
+/// variable name actually occurs (this `Expr` has no compound first-order terms -- a predicate's
+/// arguments are bare names), so this just walks down tracking which names the quantifiers
+/// passed through so far have bound.
+pub fn free_variables(e: &Expr) -> HashSet<String> {
+    fn go(e: &Expr, bound: &mut Vec<String>, out: &mut HashSet<String>) {
+        match e {
+            Expr::Bottom => {}
+            Expr::Predicate { args, .. } => {
+                for a in args {
+                    if !bound.contains(a) {
+                        out.insert(a.clone());
+                    }
+                }
+            }
+            Expr::Unop { operand, .. } => go(operand, bound, out),
+            Expr::Binop { left, right, .. } => {
+                go(left, bound, out);
+                go(right, bound, out);
+            }
+            Expr::AssocBinop { exprs, .. } => {
+                for e in exprs {
+                    go(e, bound, out);
+                }
+            }
+            Expr::Quantifier { name, body, .. } => {
+                bound.push(name.clone());
+                go(body, bound, out);
+                bound.pop();
+            }
+        }
+    }
+    let mut bound = Vec::new();
+    let mut out = HashSet::new();
+    go(e, &mut bound, &mut out);
+    out
+}
+
+/// A counter-based fresh-name source, the same shape as `util::uid()` (an `AtomicUsize` bumped
+/// on every call) but kept local to this module rather than pulled in from the `web-app` crate,
+/// which depends on this one and not the other way around.
+fn fresh_suffix() -> usize {
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+    static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+    NEXT_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Rename `expr`'s outermost bound variable from `from` to `to`, if `expr` is a `Quantifier`
+/// binding `from` (a no-op otherwise). Exposed standalone since `substitute`'s capture-avoidance
+/// step needs exactly this, and proof rules occasionally want to alpha-rename a quantifier
+/// without otherwise touching it (e.g. to line two bound variables up before comparing bodies).
+pub fn alpha_rename(e: &Expr, from: &str, to: &str) -> Expr {
+    match e {
+        Expr::Quantifier { symbol, name, body } if name == from => Expr::Quantifier {
+            symbol: *symbol,
+            name: to.to_string(),
+            body: Box::new(substitute(body, from, to)),
+        },
+        other => other.clone(),
+    }
+}
+
+/// Replace every free occurrence of the variable name `var` with `replacement` in `e`, avoiding
+/// capture: descending under a `Quantifier` binding `var` stops (it's shadowed there), and
+/// descending under a `Quantifier` binding a name equal to `replacement` first alpha-renames that
+/// binder to a fresh name, so `replacement` can't be captured by it.
+pub fn substitute(e: &Expr, var: &str, replacement: &str) -> Expr {
+    match e {
+        Expr::Bottom => Expr::Bottom,
+        Expr::Predicate { name, args } => Expr::Predicate {
+            name: name.clone(),
+            args: args.iter().map(|a| if a == var { replacement.to_string() } else { a.clone() }).collect(),
+        },
+        Expr::Unop { symbol, operand } => {
+            Expr::Unop { symbol: *symbol, operand: Box::new(substitute(operand, var, replacement)) }
+        }
+        Expr::Binop { symbol, left, right } => Expr::Binop {
+            symbol: *symbol,
+            left: Box::new(substitute(left, var, replacement)),
+            right: Box::new(substitute(right, var, replacement)),
+        },
+        Expr::AssocBinop { symbol, exprs } => Expr::AssocBinop {
+            symbol: *symbol,
+            exprs: exprs.iter().map(|e| substitute(e, var, replacement)).collect(),
+        },
+        Expr::Quantifier { symbol, name, body } => {
+            if name == var {
+                e.clone()
+            } else if name == replacement {
+                let fresh = format!("{}{}", name, fresh_suffix());
+                let renamed_body = substitute(body, name, &fresh);
+                Expr::Quantifier {
+                    symbol: *symbol,
+                    name: fresh,
+                    body: Box::new(substitute(&renamed_body, var, replacement)),
+                }
+            } else {
+                Expr::Quantifier { symbol: *symbol, name: name.clone(), body: Box::new(substitute(body, var, replacement)) }
+            }
+        }
+    }
+}
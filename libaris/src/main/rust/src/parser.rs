@@ -1,73 +1,403 @@
 use super::{Expr, USymbol, BSymbol, ASymbol, QSymbol};
 
+use std::collections::HashMap;
+
 fn custom_error<A, B>(a: A, x: u32) -> nom::IResult<A, B> {
     return Err(nom::Err::Error(nom::Context::Code(a, nom::ErrorKind::Custom(x))));
 }
 
-fn variable(s: &str) -> nom::IResult<&str, String> {
-    let r = variable_(s);
-    if let Ok((ref rest, ref var)) = r {
-        if let Ok((_, _)) = keyword(&var) {
-            return custom_error(rest, 0);
-        }
-    }
-    r
-}
-
 named!(space<&str, ()>, do_parse!(many0!(one_of!(" \t")) >> (())));
 named!(variable_<&str, String>, do_parse!(x: many1!(one_of!("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789_")) >> ({let mut y = String::new(); for c in x { y.push(c); }; y})));
-named!(keyword<&str, &str>, alt!(tag!("forall") | tag!("exists")));
 
 named!(bottom<&str, Expr>, do_parse!(tag!("_|_") >> (Expr::Bottom)));
 
-named!(notterm<&str, Expr>, do_parse!(tag!("~") >> e: paren_expr >> (Expr::Unop { symbol: USymbol::Not, operand: Box::new(e) })));
-
-named!(predicate<&str, Expr>, alt!(
-    do_parse!(space >> name: variable >> space >> tag!("(") >> space >> args: separated_list!(do_parse!(space >> tag!(",") >> space >> (())), variable) >> tag!(")") >> (Expr::Predicate { name, args })) |
-    do_parse!(space >> name: variable >> space >> (Expr::Predicate { name, args: vec![]}))
-    ));
-
 named!(forallQuantifier<&str, QSymbol>, do_parse!(alt!(tag!("forall ") | tag!("∀")) >> (QSymbol::Forall)));
 named!(existsQuantifier<&str, QSymbol>, do_parse!(alt!(tag!("exists ") | tag!("∃")) >> (QSymbol::Exists)));
 named!(quantifier<&str, QSymbol>, alt!(forallQuantifier | existsQuantifier));
-named!(binder<&str, Expr>, do_parse!(space >> symbol: quantifier >> space >> name: variable >> space >> tag!(",") >> space >> body: paren_expr >> (Expr::Quantifier { symbol, name, body: Box::new(body) })));
 
-named!(binop<&str, BSymbol>, alt!(do_parse!(tag!("->") >> (BSymbol::Implies)) | do_parse!(tag!("+") >> (BSymbol::Plus)) | do_parse!(tag!("*") >> (BSymbol::Mult))));
-named!(binopterm<&str, Expr>, do_parse!(left: paren_expr >> space >> symbol: binop >> space >> right: paren_expr >> (Expr::Binop { symbol, left: Box::new(left), right: Box::new(right) })));
+// Binding powers, tightest to loosest: `~` > `*` > `+` > `&` > `|` > `->` (right-assoc) > `<->`.
+// These are the numbers `pretty::own_precedence` ranks the same operators by, so the two stay in
+// step even though only this side's numbers currently drive any parsing decisions. They're also
+// exactly the defaults `OperatorTable::new` registers below, so a caller who never touches the
+// table gets this same grammar.
+const PREC_NOT: u8 = 100;
+const PREC_MULT: u8 = 90;
+const PREC_PLUS: u8 = 80;
+const PREC_AND: u8 = 70;
+const PREC_OR: u8 = 60;
+const PREC_IMPLIES: u8 = 50;
+const PREC_BICON: u8 = 40;
+
+/// Where an operator's spelling sits relative to its operand(s).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Fixity {
+    /// The spelling comes before a single operand, e.g. `~P`.
+    Prefix,
+    /// The spelling sits between two operands, grouping left-to-right: `A & B & C` is
+    /// `(A & B) & C`-shaped (though `Assoc` operators actually collapse a same-symbol run into
+    /// one flat node rather than nesting -- see `expr_bp`).
+    InfixLeft,
+    /// Like `InfixLeft`, but groups right-to-left: `A -> B -> C` is `A -> (B -> C)`.
+    InfixRight,
+}
+
+/// Which `Expr` node an operator builds once its operand(s) are in hand. `Expr` has exactly one
+/// prefix-shaped unary node (`Unop` with `USymbol::Not`), so `Fixity::Prefix` is only ever paired
+/// with `Not` below -- a new prefix *spelling* can be registered (an alias for negation), but not
+/// a new prefix *operation*, since there's no other `USymbol` variant for it to build.
+#[derive(Clone, Debug)]
+pub enum OpBuilder {
+    Not,
+    Assoc(ASymbol),
+    Binop(BSymbol),
+}
+
+#[derive(Clone, Debug)]
+struct OperatorEntry {
+    prec: u8,
+    fixity: Fixity,
+    builder: OpBuilder,
+}
+
+/// Why `OperatorTable::register` refused a spelling.
+#[derive(Clone, Debug)]
+pub struct RegisterError {
+    pub spelling: String,
+    pub reason: String,
+}
+
+impl std::fmt::Display for RegisterError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "can't register operator {:?}: {}", self.spelling, self.reason)
+    }
+}
+
+/// True iff `s` is a nonempty run of ASCII letters -- the shape of the word-like spellings
+/// (`forall`, `exists`, and any alphabetic operator a caller registers) that `is_reserved_prefix`
+/// has to keep out of `variable`'s lexing, on top of the symbolic spellings that can't be confused
+/// with an identifier to begin with.
+fn is_alphabetic_spelling(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(|c| c.is_ascii_alphabetic())
+}
 
-named!(andrepr<&str, ASymbol>, do_parse!(alt!(tag!("&") | tag!("∧") | tag!("/\\")) >> (ASymbol::And)));
-named!(orrepr<&str, ASymbol>, do_parse!(alt!(tag!("|") | tag!("∨") | tag!("\\/")) >> (ASymbol::Or)));
-named!(biconrepr<&str, ASymbol>, do_parse!(alt!(tag!("<->") | tag!("↔")) >> (ASymbol::Bicon)));
+/// The parser's operator set, as runtime data instead of a fixed family of `named!` combinators
+/// (`binop`, `andrepr`, `orrepr`, `biconrepr`). `OperatorTable::new` registers the built-in
+/// connectives at the precedences above; a caller that wants additional notation (a different
+/// glyph for an existing connective, say) calls `register` on its own table before handing it to
+/// `expr`/`main`.
+pub struct OperatorTable {
+    entries: HashMap<String, OperatorEntry>,
+}
 
-named!(assoctermaux<&str, (Vec<Expr>, Vec<ASymbol>)>, alt!(
-    do_parse!(space >> e: paren_expr >> space >> sym: alt!(andrepr | orrepr | biconrepr) >> space >> rec: assoctermaux >> ({ let (mut es, mut syms) = rec; es.push(e); syms.push(sym); (es, syms) })) |
-    do_parse!(e: paren_expr >> (vec![e], vec![]))
-    ));
+impl OperatorTable {
+    pub fn new() -> Self {
+        let mut table = OperatorTable { entries: HashMap::new() };
+        table.register_defaults();
+        table
+    }
 
-fn assocterm(s: &str) -> nom::IResult<&str, Expr> {
-    let (rest, (mut exprs, syms)) = assoctermaux(s)?;
-    assert_eq!(exprs.len(), syms.len()+1);
-    if exprs.len() == 1 {
-        return custom_error(rest, 0);
+    fn register_defaults(&mut self) {
+        // Infallible: these spellings are pairwise distinct, and each fixity/builder pairing here
+        // is one `register` already allows, so none of these `.unwrap()`s can fire.
+        self.register("<->", PREC_BICON, Fixity::InfixLeft, OpBuilder::Assoc(ASymbol::Bicon)).unwrap();
+        self.register("↔", PREC_BICON, Fixity::InfixLeft, OpBuilder::Assoc(ASymbol::Bicon)).unwrap();
+        self.register("->", PREC_IMPLIES, Fixity::InfixRight, OpBuilder::Binop(BSymbol::Implies)).unwrap();
+        self.register("→", PREC_IMPLIES, Fixity::InfixRight, OpBuilder::Binop(BSymbol::Implies)).unwrap();
+        self.register("/\\", PREC_AND, Fixity::InfixLeft, OpBuilder::Assoc(ASymbol::And)).unwrap();
+        self.register("&", PREC_AND, Fixity::InfixLeft, OpBuilder::Assoc(ASymbol::And)).unwrap();
+        self.register("∧", PREC_AND, Fixity::InfixLeft, OpBuilder::Assoc(ASymbol::And)).unwrap();
+        self.register("\\/", PREC_OR, Fixity::InfixLeft, OpBuilder::Assoc(ASymbol::Or)).unwrap();
+        self.register("|", PREC_OR, Fixity::InfixLeft, OpBuilder::Assoc(ASymbol::Or)).unwrap();
+        self.register("∨", PREC_OR, Fixity::InfixLeft, OpBuilder::Assoc(ASymbol::Or)).unwrap();
+        self.register("+", PREC_PLUS, Fixity::InfixLeft, OpBuilder::Binop(BSymbol::Plus)).unwrap();
+        self.register("*", PREC_MULT, Fixity::InfixLeft, OpBuilder::Binop(BSymbol::Mult)).unwrap();
+        self.register("~", PREC_NOT, Fixity::Prefix, OpBuilder::Not).unwrap();
+        self.register("¬", PREC_NOT, Fixity::Prefix, OpBuilder::Not).unwrap();
     }
-    let mut symbol = syms[0].clone();
-    if !syms.iter().all(|x| x == &symbol) {
-        return custom_error(rest, 0);
+
+    /// Add `spelling` to the table at `prec`/`fixity`, building `builder` once its operand(s) are
+    /// parsed. Errors instead of silently overriding if `spelling` is already registered with a
+    /// different fixity (re-registering the same spelling/fixity pair, e.g. to change its
+    /// precedence, is allowed), or if `fixity` is `Prefix` paired with anything other than
+    /// `OpBuilder::Not` (the only prefix-shaped node `Expr` has).
+    pub fn register(&mut self, spelling: &str, prec: u8, fixity: Fixity, builder: OpBuilder) -> Result<(), RegisterError> {
+        if let Some(existing) = self.entries.get(spelling) {
+            if existing.fixity != fixity {
+                return Err(RegisterError {
+                    spelling: spelling.to_string(),
+                    reason: format!("already registered with {:?} fixity, not {:?}", existing.fixity, fixity),
+                });
+            }
+        }
+        if fixity == Fixity::Prefix && !matches!(builder, OpBuilder::Not) {
+            return Err(RegisterError {
+                spelling: spelling.to_string(),
+                reason: "a Prefix operator can only build Unop::Not -- Expr has no other prefix-shaped node".to_string(),
+            });
+        }
+        self.entries.insert(spelling.to_string(), OperatorEntry { prec, fixity, builder });
+        Ok(())
+    }
+
+    /// The longest registered spelling of fixity `Prefix` (if `want_prefix`) or not (otherwise)
+    /// that `s` starts with. Longest-first so e.g. a later-registered multi-character alias isn't
+    /// shadowed by a shorter spelling that happens to be one of its prefixes.
+    fn try_match(&self, s: &str, want_prefix: bool) -> Option<(&str, &OperatorEntry)> {
+        self.entries
+            .iter()
+            .filter(|(_, e)| (e.fixity == Fixity::Prefix) == want_prefix)
+            .filter(|(spelling, _)| s.starts_with(spelling.as_str()))
+            .max_by_key(|(spelling, _)| spelling.len())
+            .map(|(spelling, e)| (spelling.as_str(), e))
+    }
+
+    fn try_prefix(&self, s: &str) -> Option<(&str, &OperatorEntry)> {
+        self.try_match(s, true)
+    }
+
+    fn try_infix(&self, s: &str) -> Option<(&str, &OperatorEntry)> {
+        self.try_match(s, false)
+    }
+
+    /// True iff `var` (a full identifier `variable_` already captured) begins with a word-like
+    /// reserved spelling -- `forall`/`exists`, or any alphabetic operator a caller registered --
+    /// and so can't also be parsed as a predicate/variable name.
+    fn is_reserved_prefix(&self, var: &str) -> bool {
+        if var.starts_with("forall") || var.starts_with("exists") {
+            return true;
+        }
+        self.entries.keys().any(|spelling| is_alphabetic_spelling(spelling) && var.starts_with(spelling.as_str()))
     }
-    exprs.reverse();
-    Ok((rest, Expr::AssocBinop { symbol, exprs }))
 }
 
-named!(paren_expr<&str, Expr>, alt!(bottom | predicate | notterm | binder | do_parse!(space >> tag!("(") >> space >> e: expr >> space >> tag!(")") >> space >> (e))));
-named!(pub expr<&str, Expr>, alt!(assocterm | binopterm | paren_expr));
-named!(pub main<&str, Expr>, do_parse!(e: expr >> tag!("\n") >> (e)));
+impl Default for OperatorTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn variable<'a>(s: &'a str, table: &OperatorTable) -> nom::IResult<&'a str, String> {
+    let r = variable_(s);
+    if let Ok((rest, ref var)) = r {
+        if table.is_reserved_prefix(var) {
+            return custom_error(rest, 0);
+        }
+    }
+    r
+}
+
+fn predicate<'a>(s: &'a str, table: &OperatorTable) -> nom::IResult<&'a str, Expr> {
+    alt!(s,
+        do_parse!(space >> name: call!(variable, table) >> space >> tag!("(") >> space >> args: separated_list!(do_parse!(space >> tag!(",") >> space >> (())), call!(variable, table)) >> tag!(")") >> (Expr::Predicate { name, args })) |
+        do_parse!(space >> name: call!(variable, table) >> space >> (Expr::Predicate { name, args: vec![] }))
+    )
+}
+
+fn binder<'a>(s: &'a str, table: &OperatorTable) -> nom::IResult<&'a str, Expr> {
+    do_parse!(s,
+        space >> symbol: quantifier >> space >> name: call!(variable, table) >> space >> tag!(",") >> space >>
+        body: call!(binder_body, table) >>
+        (Expr::Quantifier { symbol, name, body: Box::new(body) })
+    )
+}
+
+/// A quantifier's body is parsed at the loosest precedence, so it extends as far right as it
+/// can -- `forall x, A & B -> C` is `forall x, (A & B -> C)`, not `(forall x, A & B) -> C` --
+/// matching the usual mathematical convention that a quantifier's scope is everything to its
+/// right unless parentheses say otherwise.
+fn binder_body<'a>(s: &'a str, table: &OperatorTable) -> nom::IResult<&'a str, Expr> {
+    expr_bp(s, table, 0)
+}
+
+/// A parenthesized expression is the only way to embed a full (non-atomic) expression inside
+/// another one below the top level -- see `primary`.
+fn parenthesized<'a>(s: &'a str, table: &OperatorTable) -> nom::IResult<&'a str, Expr> {
+    expr_bp(s, table, 0)
+}
+
+/// The atoms this grammar's precedence climbing bottoms out on: `_|_` or `⊥`, a predicate application
+/// (or bare 0-ary predicate), a table-registered prefix operator applied to another primary, a
+/// quantifier, or a fully parenthesized expression.
+fn primary<'a>(s: &'a str, table: &OperatorTable) -> nom::IResult<&'a str, Expr> {
+    if let Some((spelling, entry)) = table.try_prefix(s) {
+        let prec = entry.prec;
+        let builder = entry.builder.clone();
+        let rest = &s[spelling.len()..];
+        let (rest, operand) = expr_bp(rest, table, prec)?;
+        let node = match builder {
+            OpBuilder::Not => Expr::Unop { symbol: USymbol::Not, operand: Box::new(operand) },
+            OpBuilder::Assoc(_) | OpBuilder::Binop(_) => {
+                unreachable!("register() only ever pairs Fixity::Prefix with OpBuilder::Not")
+            }
+        };
+        return Ok((rest, node));
+    }
+    alt!(s,
+        bottom |
+        do_parse!(tag!("⊥") >> (Expr::Bottom)) |
+        call!(predicate, table) |
+        call!(binder, table) |
+        do_parse!(space >> tag!("(") >> space >> e: call!(parenthesized, table) >> space >> tag!(")") >> space >> (e))
+    )
+}
+
+/// Precedence-climbing (Pratt) parser: parse a primary, then repeatedly extend it with any infix
+/// operator in `table` whose precedence is at least `min_bp`, recursing for the right operand
+/// with `min_bp = prec` (`InfixRight`) or `prec + 1` (`InfixLeft`). A run of the same associative
+/// symbol (`&`, `|`, `<->`, under any of their registered spellings) at the same precedence level
+/// collapses into a single `Expr::AssocBinop` with every operand, rather than nesting pairwise --
+/// so different symbols can never end up mixed into one `AssocBinop`, the same invariant the old
+/// `assocterm` combinator's explicit check used to enforce, just now guaranteed structurally
+/// because each precedence level only ever has one associative symbol registered on it.
+fn expr_bp<'a>(s: &'a str, table: &OperatorTable, min_bp: u8) -> nom::IResult<&'a str, Expr> {
+    let (mut rest, mut lhs) = primary(s, table)?;
+    loop {
+        let (after_space, _) = space(rest)?;
+        let (spelling, entry) = match table.try_infix(after_space) {
+            Some((spelling, entry)) if entry.prec >= min_bp => (spelling, entry),
+            _ => break,
+        };
+        let op_rest = &after_space[spelling.len()..];
+        let (after_op_space, _) = space(op_rest)?;
+        let prec = entry.prec;
+        let builder = entry.builder.clone();
+        let next_min_bp = match entry.fixity {
+            Fixity::InfixRight => prec,
+            _ => prec + 1,
+        };
+        match builder {
+            OpBuilder::Binop(symbol) => {
+                let (r, rhs) = expr_bp(after_op_space, table, next_min_bp)?;
+                lhs = Expr::Binop { symbol, left: Box::new(lhs), right: Box::new(rhs) };
+                rest = r;
+            }
+            OpBuilder::Assoc(symbol) => {
+                let (r, rhs) = expr_bp(after_op_space, table, next_min_bp)?;
+                let mut exprs = vec![lhs, rhs];
+                rest = r;
+                loop {
+                    let (after_space2, _) = space(rest)?;
+                    match table.try_infix(after_space2) {
+                        Some((spelling2, entry2)) if matches!(&entry2.builder, OpBuilder::Assoc(s2) if *s2 == symbol) => {
+                            let op_rest2 = &after_space2[spelling2.len()..];
+                            let (after_op_space2, _) = space(op_rest2)?;
+                            let (r2, rhs2) = expr_bp(after_op_space2, table, next_min_bp)?;
+                            exprs.push(rhs2);
+                            rest = r2;
+                        }
+                        _ => break,
+                    }
+                }
+                lhs = Expr::AssocBinop { symbol, exprs };
+            }
+            OpBuilder::Not => unreachable!("register() never pairs a non-Prefix fixity with OpBuilder::Not"),
+        }
+    }
+    Ok((rest, lhs))
+}
+
+pub fn expr<'a>(s: &'a str, table: &OperatorTable) -> nom::IResult<&'a str, Expr> {
+    expr_bp(s, table, 0)
+}
+
+pub fn main<'a>(s: &'a str, table: &OperatorTable) -> nom::IResult<&'a str, Expr> {
+    do_parse!(s, e: call!(expr, table) >> tag!("\n") >> (e))
+}
 
 #[test]
 fn test() {
-    println!("{:?}", predicate("a(   b, c)"));
-    println!("{:?}", expr("a & b & c(x,y)\n"));
-    println!("{:?}", expr("forall a, (b & c)\n"));
-    println!("{:?}", expr("exists x, (Tet(x) & SameCol(x, b)) -> ~forall x, (Tet(x) -> LeftOf(x, b))\n"));
+    let table = OperatorTable::new();
+    println!("{:?}", predicate("a(   b, c)", &table));
+    println!("{:?}", expr("a & b & c(x,y)\n", &table));
+    println!("{:?}", expr("forall a, (b & c)\n", &table));
+    println!("{:?}", expr("exists x, (Tet(x) & SameCol(x, b)) -> ~forall x, (Tet(x) -> LeftOf(x, b))\n", &table));
     named!(f<&str, Vec<&str>>, many1!(tag!("a")));
     println!("{:?}", f("aa\n"));
 }
+
+#[test]
+fn test_precedence_climbing() {
+    let table = OperatorTable::new();
+    let p = |s: &str| expr(s, &table).unwrap().1;
+    let var = |n: &str| Expr::Predicate { name: n.to_string(), args: vec![] };
+
+    // `->` binds looser than `&`: no more explicit grouping needed for this.
+    assert_eq!(
+        p("A -> B & C"),
+        Expr::Binop {
+            symbol: BSymbol::Implies,
+            left: Box::new(var("A")),
+            right: Box::new(Expr::AssocBinop { symbol: ASymbol::And, exprs: vec![var("B"), var("C")] }),
+        }
+    );
+
+    // `&` binds tighter than `|`, and a run of `|` collapses into one AssocBinop.
+    assert_eq!(
+        p("A & B | C | D"),
+        Expr::AssocBinop {
+            symbol: ASymbol::Or,
+            exprs: vec![
+                Expr::AssocBinop { symbol: ASymbol::And, exprs: vec![var("A"), var("B")] },
+                var("C"),
+                var("D"),
+            ],
+        }
+    );
+
+    // `->` is right-associative.
+    assert_eq!(
+        p("A -> B -> C"),
+        Expr::Binop {
+            symbol: BSymbol::Implies,
+            left: Box::new(var("A")),
+            right: Box::new(Expr::Binop { symbol: BSymbol::Implies, left: Box::new(var("B")), right: Box::new(var("C")) }),
+        }
+    );
+
+    // `A & B -> C | D` used to require `(A & B) -> (C | D)` written out explicitly.
+    assert_eq!(
+        p("A & B -> C | D"),
+        Expr::Binop {
+            symbol: BSymbol::Implies,
+            left: Box::new(Expr::AssocBinop { symbol: ASymbol::And, exprs: vec![var("A"), var("B")] }),
+            right: Box::new(Expr::AssocBinop { symbol: ASymbol::Or, exprs: vec![var("C"), var("D")] }),
+        }
+    );
+
+    // A quantifier's body extends as far right as it can.
+    assert_eq!(
+        p("forall x, P(x) -> Q(x)"),
+        Expr::Quantifier {
+            symbol: QSymbol::Forall,
+            name: "x".to_string(),
+            body: Box::new(Expr::Binop {
+                symbol: BSymbol::Implies,
+                left: Box::new(Expr::Predicate { name: "P".to_string(), args: vec!["x".to_string()] }),
+                right: Box::new(Expr::Predicate { name: "Q".to_string(), args: vec!["x".to_string()] }),
+            }),
+        }
+    );
+}
+
+#[test]
+fn test_custom_operator() {
+    // A caller can register a new spelling (here, a word-like alias for negation) before parsing.
+    let mut table = OperatorTable::new();
+    table.register("not ", PREC_NOT, Fixity::Prefix, OpBuilder::Not).unwrap();
+    let e = expr("not A & B\n", &table).unwrap().1;
+    assert_eq!(
+        e,
+        Expr::AssocBinop {
+            symbol: ASymbol::And,
+            exprs: vec![
+                Expr::Unop { symbol: USymbol::Not, operand: Box::new(Expr::Predicate { name: "A".to_string(), args: vec![] }) },
+                Expr::Predicate { name: "B".to_string(), args: vec![] },
+            ],
+        }
+    );
+
+    // Registering an existing spelling under a different fixity is rejected rather than silently
+    // overriding it.
+    let mut table = OperatorTable::new();
+    assert!(table.register("&", PREC_NOT, Fixity::Prefix, OpBuilder::Not).is_err());
+}
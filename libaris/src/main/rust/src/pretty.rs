@@ -0,0 +1,229 @@
+//! The inverse of `parser`: render an `Expr` back to a string that reparses to an equal `Expr`.
+//!
+//! `parser::expr_bp` is a precedence-climbing parser (tightest to loosest: `~`, `*`, `+`, `&`,
+//! `|`, `->` (right-assoc), `<->`), so an operand only needs parens when printing it unwrapped
+//! would actually change how it reparses -- not unconditionally the way a mandatory-parens
+//! grammar would require. `render` threads the enclosing operator's real binding power into
+//! `min_prec` to decide that per operand, mirroring the `min_bp` each operand was itself parsed
+//! at in `expr_bp`: same precedence is safe on the side that recurses there (the rhs of `->`, the
+//! lhs of `*`/`+`/the `Assoc` operators), but needs to force a paren on the other side, where a
+//! same-precedence child would otherwise get silently reassociated by the parser into a different
+//! tree than the one being printed.
+use super::{ASymbol, BSymbol, Expr, QSymbol, USymbol};
+
+/// Selects which spelling `to_string_with` uses for each connective.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum PrettyMode {
+    Ascii,
+    Unicode,
+}
+
+/// Binding power required of a context for a node to print unwrapped; `ATOMIC` means the node is
+/// one of `parser::primary`'s closed alternatives and never needs wrapping.
+const ATOMIC: u8 = u8::MAX;
+
+fn own_precedence(e: &Expr) -> u8 {
+    match e {
+        Expr::Bottom | Expr::Predicate { .. } | Expr::Unop { .. } => ATOMIC,
+        // A quantifier's body runs as far right as it can (see `parser::binder_body`), so a
+        // quantifier prints unwrapped only where the context is already the loosest level.
+        Expr::Quantifier { .. } => 0,
+        Expr::AssocBinop { symbol, .. } => match symbol {
+            ASymbol::And => 70,
+            ASymbol::Or => 60,
+            ASymbol::Bicon => 40,
+        },
+        Expr::Binop { symbol, .. } => match symbol {
+            BSymbol::Mult => 90,
+            BSymbol::Plus => 80,
+            BSymbol::Implies => 50,
+        },
+    }
+}
+
+fn not_symbol(mode: PrettyMode) -> &'static str {
+    match mode {
+        PrettyMode::Ascii => "~",
+        PrettyMode::Unicode => "¬",
+    }
+}
+
+fn assoc_symbol(symbol: ASymbol, mode: PrettyMode) -> &'static str {
+    match (symbol, mode) {
+        (ASymbol::And, PrettyMode::Ascii) => "&",
+        (ASymbol::And, PrettyMode::Unicode) => "∧",
+        (ASymbol::Or, PrettyMode::Ascii) => "|",
+        (ASymbol::Or, PrettyMode::Unicode) => "∨",
+        (ASymbol::Bicon, PrettyMode::Ascii) => "<->",
+        (ASymbol::Bicon, PrettyMode::Unicode) => "↔",
+    }
+}
+
+fn binop_symbol(symbol: BSymbol, mode: PrettyMode) -> &'static str {
+    match (symbol, mode) {
+        (BSymbol::Implies, PrettyMode::Ascii) => "->",
+        (BSymbol::Implies, PrettyMode::Unicode) => "→",
+        (BSymbol::Plus, _) => "+",
+        (BSymbol::Mult, _) => "*",
+    }
+}
+
+fn quant_symbol(symbol: QSymbol, mode: PrettyMode) -> &'static str {
+    match (symbol, mode) {
+        (QSymbol::Forall, PrettyMode::Ascii) => "forall ",
+        (QSymbol::Forall, PrettyMode::Unicode) => "∀",
+        (QSymbol::Exists, PrettyMode::Ascii) => "exists ",
+        (QSymbol::Exists, PrettyMode::Unicode) => "∃",
+    }
+}
+
+/// Gather `exprs` into `out`, recursing into any immediate child that's itself an `AssocBinop`
+/// on the same `symbol` -- so a tree built as nested binary `And`s prints as one flat
+/// `a & b & c` rather than `a & (b & c)`, matching how `parser::expr_bp` always parses a run of the
+/// same symbol into a single `AssocBinop`.
+fn flatten_assoc<'a>(exprs: &'a [Expr], symbol: ASymbol, out: &mut Vec<&'a Expr>) {
+    for e in exprs {
+        match e {
+            Expr::AssocBinop { symbol: inner_symbol, exprs: inner_exprs } if *inner_symbol == symbol => {
+                flatten_assoc(inner_exprs, symbol, out);
+            }
+            _ => out.push(e),
+        }
+    }
+}
+
+fn render(e: &Expr, mode: PrettyMode, min_prec: u8) -> String {
+    let body = match e {
+        Expr::Bottom => match mode {
+            PrettyMode::Ascii => "_|_".to_string(),
+            PrettyMode::Unicode => "⊥".to_string(),
+        },
+        Expr::Predicate { name, args } => {
+            if args.is_empty() {
+                name.clone()
+            } else {
+                format!("{}({})", name, args.join(", "))
+            }
+        }
+        Expr::Unop { symbol: USymbol::Not, operand } => {
+            format!("{}{}", not_symbol(mode), render(operand, mode, ATOMIC))
+        }
+        Expr::Binop { symbol, left, right } => {
+            let prec = own_precedence(e);
+            // `->` is the only right-associative operator (see `parser::expr_bp`): its rhs
+            // absorbs a same-precedence chain without needing parens, but a same-precedence lhs
+            // would get reparsed as part of that chain unless wrapped. `*`/`+` are left-
+            // associative, the mirror image: the lhs chain prints flat, the rhs needs wrapping.
+            let (left_min, right_min) = match symbol {
+                BSymbol::Implies => (prec + 1, prec),
+                BSymbol::Plus | BSymbol::Mult => (prec, prec + 1),
+            };
+            format!(
+                "{} {} {}",
+                render(left, mode, left_min),
+                binop_symbol(*symbol, mode),
+                render(right, mode, right_min)
+            )
+        }
+        Expr::AssocBinop { symbol, exprs } => {
+            // `&`/`|`/`<->` are all left-associative, but `flatten_assoc` already collapsed any
+            // same-symbol run into this one node, so no child can tie this node's own precedence
+            // without being the same symbol (already flattened away) -- every child can print at
+            // this node's own precedence without risking a different reparse.
+            let prec = own_precedence(e);
+            let mut flat = Vec::new();
+            flatten_assoc(exprs, *symbol, &mut flat);
+            let sep = format!(" {} ", assoc_symbol(*symbol, mode));
+            flat.iter().map(|e| render(e, mode, prec)).collect::<Vec<_>>().join(&sep)
+        }
+        Expr::Quantifier { symbol, name, body } => {
+            // `binder_body` parses the body at `min_bp = 0`, extending as far right as it can, so
+            // it never needs wrapping on precedence grounds alone.
+            format!("{}{}, {}", quant_symbol(*symbol, mode), name, render(body, mode, 0))
+        }
+    };
+    if own_precedence(e) < min_prec {
+        format!("({})", body)
+    } else {
+        body
+    }
+}
+
+impl Expr {
+    /// Render `self` as a string in the given `mode`, wrapping exactly the operands the grammar
+    /// requires to be wrapped -- guaranteed so that `parser::expr(&e.to_string_with(mode))`
+    /// parses back to `Ok((_, e'))` with `e' == e` (modulo `AssocBinop` nesting, which this
+    /// flattens, and which the parser would have flattened on its own anyway).
+    pub fn to_string_with(&self, mode: PrettyMode) -> String {
+        render(self, mode, 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{expr, OperatorTable};
+
+    fn var(n: &str) -> Expr {
+        Expr::Predicate { name: n.to_string(), args: vec![] }
+    }
+
+    fn roundtrips(e: &Expr) -> bool {
+        let table = OperatorTable::new();
+        let rendered = e.to_string_with(PrettyMode::Ascii);
+        matches!(expr(&format!("{}\n", rendered), &table), Ok((_, e2)) if e2 == *e)
+    }
+
+    #[test]
+    fn test_no_parens_needed_inside_and_under_implies() {
+        // `P -> (Q & R)` doesn't need parens: `&` binds tighter than `->`.
+        let e = Expr::Binop {
+            symbol: BSymbol::Implies,
+            left: Box::new(var("P")),
+            right: Box::new(Expr::AssocBinop { symbol: ASymbol::And, exprs: vec![var("Q"), var("R")] }),
+        };
+        assert_eq!(e.to_string_with(PrettyMode::Ascii), "P -> Q & R");
+        assert!(roundtrips(&e));
+    }
+
+    #[test]
+    fn test_parens_needed_for_same_precedence_lhs_of_implies() {
+        // (P -> Q) -> R does need parens: `->` is right-associative, so printing this unwrapped
+        // would reparse as `P -> (Q -> R)`, a different tree.
+        let e = Expr::Binop {
+            symbol: BSymbol::Implies,
+            left: Box::new(Expr::Binop { symbol: BSymbol::Implies, left: Box::new(var("P")), right: Box::new(var("Q")) }),
+            right: Box::new(var("R")),
+        };
+        assert_eq!(e.to_string_with(PrettyMode::Ascii), "(P -> Q) -> R");
+        assert!(roundtrips(&e));
+    }
+
+    #[test]
+    fn test_no_parens_needed_for_same_precedence_rhs_of_implies() {
+        // P -> (Q -> R) needs no parens: the rhs of a right-associative `->` absorbs a
+        // same-precedence chain without being reassociated.
+        let e = Expr::Binop {
+            symbol: BSymbol::Implies,
+            left: Box::new(var("P")),
+            right: Box::new(Expr::Binop { symbol: BSymbol::Implies, left: Box::new(var("Q")), right: Box::new(var("R")) }),
+        };
+        assert_eq!(e.to_string_with(PrettyMode::Ascii), "P -> Q -> R");
+        assert!(roundtrips(&e));
+    }
+
+    #[test]
+    fn test_quantifier_body_needs_no_parens() {
+        let e = Expr::Quantifier {
+            symbol: QSymbol::Forall,
+            name: "x".to_string(),
+            body: Box::new(Expr::Binop {
+                symbol: BSymbol::Implies,
+                left: Box::new(var("P")),
+                right: Box::new(var("Q")),
+            }),
+        };
+        assert_eq!(e.to_string_with(PrettyMode::Ascii), "forall x, P -> Q");
+        assert!(roundtrips(&e));
+    }
+}
@@ -2,7 +2,8 @@ use super::*;
 use frunk::Coproduct::{Inl, Inr};
 
 pub fn demo_proof_1<P: Proof>() -> P where P: PartialEq+std::fmt::Debug, P::Reference: PartialEq+std::fmt::Debug {
-    let p = |s: &str| { let t = format!("{}\n", s); parser::main(&t).unwrap().1 };
+    let table = parser::OperatorTable::new();
+    let p = |s: &str| { let t = format!("{}\n", s); parser::main(&t, &table).unwrap().1 };
     let mut prf = P::new();
     let r1 = prf.add_premise(p("A"));
     let r2 = prf.add_premise(p("B"));
@@ -23,7 +24,8 @@ pub fn demo_proof_1<P: Proof>() -> P where P: PartialEq+std::fmt::Debug, P::Refe
 }
 
 pub fn demo_proof_2<P: Proof>() -> P {
-    let p = |s: &str| { let t = format!("{}\n", s); parser::main(&t).unwrap().1 };
+    let table = parser::OperatorTable::new();
+    let p = |s: &str| { let t = format!("{}\n", s); parser::main(&t, &table).unwrap().1 };
     let mut prf = P::new();
     let r1 = prf.add_premise(p("A & B & C & D")); // 1
     let r2 = prf.add_premise(p("E | F")); // 2
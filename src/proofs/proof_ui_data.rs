@@ -1,4 +1,6 @@
 use crate::proofs::js_to_pjs;
+use crate::rules::LogicMode;
+use crate::rules::RuleT;
 use crate::PJRef;
 use crate::Proof;
 
@@ -9,6 +11,22 @@ use frunk::Coproduct;
 pub struct ProofUiData<P: Proof> {
     pub ref_to_line_depth: HashMap<PJRef<P>, (usize, usize)>,
     pub ref_to_input: HashMap<PJRef<P>, String>,
+    /// Preorder traversal of every ref in the proof, in the same order
+    /// `calculate_lineinfo_helper` assigns line numbers. Exposed via `order()`; also recomputed
+    /// by `refresh_lineinfo` so callers always have the latest traversal order on hand.
+    ref_order: Vec<PJRef<P>>,
+    /// For a justification line, the refs its rule application directly cites as dependencies.
+    pub ref_to_dependencies: HashMap<PJRef<P>, Vec<PJRef<P>>>,
+    /// The reverse of `ref_to_dependencies`: for a ref, every justification line that cites it
+    /// as a dependency. Powers "find references"/highlight-related and dead-line detection.
+    pub ref_to_dependents: HashMap<PJRef<P>, Vec<PJRef<P>>>,
+    /// For a justification line, the subproofs its rule application cites as subproof
+    /// dependencies (e.g. the `2-3` in an `ImpIntro` citing subproof lines 2 through 3).
+    /// Kept separate from `ref_to_dependencies` because `P::SubproofReference` isn't a `PJRef`.
+    pub ref_to_subproof_dependencies: HashMap<PJRef<P>, Vec<P::SubproofReference>>,
+    /// Per-line inlay hint: rule name, resolved dependency line numbers (e.g. "1, 3-4"), and a
+    /// validity marker, so the renderer doesn't need to re-derive them from the raw refs.
+    ref_to_annotation: HashMap<PJRef<P>, String>,
 }
 
 impl<P: Proof> ProofUiData<P> {
@@ -16,22 +34,98 @@ impl<P: Proof> ProofUiData<P> {
         Self {
             ref_to_line_depth: HashMap::new(),
             ref_to_input: HashMap::new(),
+            ref_order: Vec::new(),
+            ref_to_dependencies: HashMap::new(),
+            ref_to_dependents: HashMap::new(),
+            ref_to_subproof_dependencies: HashMap::new(),
+            ref_to_annotation: HashMap::new(),
         }
     }
 
     pub fn from_proof(proof: &P) -> Self {
+        let mut ref_order = Vec::new();
+        let ref_to_line_depth =
+            calculate_lineinfo_ordered::<P>(proof.top_level_proof(), &mut ref_order);
+        let (ref_to_dependencies, ref_to_dependents, ref_to_subproof_dependencies) =
+            calculate_dependency_indices(proof);
+        let ref_to_annotation = calculate_annotations(proof, &ref_to_line_depth);
         ProofUiData {
-            ref_to_line_depth: calculate_lineinfo::<P>(proof.top_level_proof()),
+            ref_to_line_depth,
             ref_to_input: initialize_inputs(proof),
+            ref_order,
+            ref_to_dependencies,
+            ref_to_dependents,
+            ref_to_subproof_dependencies,
+            ref_to_annotation,
         }
     }
+
+    /// The inlay hint for `r` (rule name, resolved dependency lines, validity marker), if `r`
+    /// is a justification line. `None` for premises, which have nothing to annotate.
+    pub fn annotation_of(&self, r: &PJRef<P>) -> Option<&str> {
+        self.ref_to_annotation.get(r).map(String::as_str)
+    }
+
+    /// Every ref that cites `r` as a dependency of its justification. Empty for premises,
+    /// and for justification lines nothing else depends on (dead-line candidates, along with
+    /// not being the proof's conclusion).
+    pub fn dependents_of(&self, r: &PJRef<P>) -> &[PJRef<P>] {
+        self.ref_to_dependents
+            .get(r)
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+
+    /// The refs `r`'s own justification cites as dependencies; empty for premises.
+    pub fn dependencies_of(&self, r: &PJRef<P>) -> &[PJRef<P>] {
+        self.ref_to_dependencies
+            .get(r)
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+
+    /// Recompute `ref_order` and `ref_to_line_depth` after any edit to `proof`.
+    ///
+    /// This was previously named `update_after` and took an `edited: PJRef<P>` naming the
+    /// changed line, as though it could re-stamp only that line's tail. It couldn't: `Proof`/
+    /// `Subproof` expose no way to find the subproof containing a given ref, or to resume a
+    /// preorder traversal partway through, without walking from the root -- the same walk
+    /// `calculate_lineinfo_helper` does here and `from_proof` does on first load. There's no
+    /// splice to land without that primitive, so rather than keep an `edited` parameter that
+    /// looked load-bearing and wasn't, it's dropped; call this the same way after every edit,
+    /// full stop. What this still saves over calling `from_proof` again is the rest of the
+    /// indices -- `ref_to_input`, `ref_to_dependencies`, `ref_to_dependents`, and
+    /// `ref_to_subproof_dependencies` are left as they were, since an edit to one line's text or
+    /// depth doesn't invalidate any of those. `ref_to_annotation` is rebuilt, since it embeds
+    /// resolved line numbers and each line's validity marker.
+    pub fn refresh_lineinfo(&mut self, proof: &P) {
+        let top = proof.top_level_proof();
+        let mut new_order = Vec::new();
+        self.ref_to_line_depth = calculate_lineinfo_ordered::<P>(top, &mut new_order);
+        self.ref_order = new_order;
+        self.ref_to_annotation = calculate_annotations(proof, &self.ref_to_line_depth);
+    }
+
+    pub fn order(&self) -> &[PJRef<P>] {
+        &self.ref_order
+    }
 }
 
 pub fn calculate_lineinfo<P: Proof>(
     proof: &<P as Proof>::Subproof,
+) -> HashMap<PJRef<P>, (usize, usize)> {
+    let mut discard_order = Vec::new();
+    calculate_lineinfo_ordered::<P>(proof, &mut discard_order)
+}
+
+/// Like `calculate_lineinfo`, but also records the preorder traversal order into `order_out`,
+/// which `ProofUiData` keeps around as `ref_order` for `order()` to hand back out.
+pub fn calculate_lineinfo_ordered<P: Proof>(
+    proof: &<P as Proof>::Subproof,
+    order_out: &mut Vec<PJRef<P>>,
 ) -> HashMap<PJRef<P>, (usize, usize)> {
     let mut ret = HashMap::new();
-    calculate_lineinfo_helper::<P>(&mut ret, proof.top_level_proof(), &mut 1, &mut 0);
+    calculate_lineinfo_helper::<P>(&mut ret, proof.top_level_proof(), &mut 1, &mut 0, order_out);
     ret
 }
 
@@ -40,16 +134,21 @@ fn calculate_lineinfo_helper<P: Proof>(
     prf: &<P as Proof>::Subproof,
     line: &mut usize,
     depth: &mut usize,
+    order_out: &mut Vec<PJRef<P>>,
 ) {
     for prem in prf.premises() {
-        output.insert(Coproduct::inject(prem.clone()), (*line, *depth));
+        let r = Coproduct::inject(prem.clone());
+        output.insert(r.clone(), (*line, *depth));
+        order_out.push(r);
         *line += 1;
     }
     for lineref in prf.lines() {
         use frunk::Coproduct::{Inl, Inr};
         match lineref {
             Inl(r) => {
-                output.insert(Coproduct::inject(r), (*line, *depth));
+                let r = Coproduct::inject(r);
+                output.insert(r.clone(), (*line, *depth));
+                order_out.push(r);
                 *line += 1;
             }
             Inr(Inl(sr)) => {
@@ -59,6 +158,7 @@ fn calculate_lineinfo_helper<P: Proof>(
                     &prf.lookup_subproof(&sr).unwrap(),
                     line,
                     depth,
+                    order_out,
                 );
                 *depth -= 1;
             }
@@ -97,3 +197,120 @@ pub fn initialize_inputs<P: Proof>(prf: &P) -> HashMap<PJRef<P>, String> {
     aux::<P>(prf.top_level_proof(), &mut out);
     out
 }
+
+/// Walk every justification line in the proof and build both the forward
+/// (line -> what it cites) and reverse (line -> what cites it) dependency indices, plus the
+/// justification -> subproof-dependency map. Reuses the same coproduct walk as
+/// `initialize_inputs`, just reading `lookup_step`'s deps/sdeps instead of formatting an expr.
+#[allow(clippy::type_complexity)]
+fn calculate_dependency_indices<P: Proof>(
+    prf: &P,
+) -> (
+    HashMap<PJRef<P>, Vec<PJRef<P>>>,
+    HashMap<PJRef<P>, Vec<PJRef<P>>>,
+    HashMap<PJRef<P>, Vec<P::SubproofReference>>,
+) {
+    fn aux<P: Proof>(
+        p: &<P as Proof>::Subproof,
+        deps_out: &mut HashMap<PJRef<P>, Vec<PJRef<P>>>,
+        dependents_out: &mut HashMap<PJRef<P>, Vec<PJRef<P>>>,
+        sdeps_out: &mut HashMap<PJRef<P>, Vec<P::SubproofReference>>,
+    ) {
+        use frunk::Coproduct::{Inl, Inr};
+        for lineref in p.lines() {
+            match lineref {
+                Inl(jr) => {
+                    let citing = Coproduct::inject(jr.clone());
+                    if let Some((_, _, deps, sdeps)) = p.lookup_step(&jr) {
+                        for dep in deps.iter() {
+                            dependents_out
+                                .entry(dep.clone())
+                                .or_insert_with(Vec::new)
+                                .push(citing.clone());
+                        }
+                        deps_out.insert(citing.clone(), deps);
+                        if !sdeps.is_empty() {
+                            sdeps_out.insert(citing, sdeps);
+                        }
+                    }
+                }
+                Inr(Inl(sr)) => aux::<P>(&p.lookup_subproof(&sr).unwrap(), deps_out, dependents_out, sdeps_out),
+                Inr(Inr(void)) => match void {},
+            }
+        }
+    }
+
+    let mut deps_out = HashMap::new();
+    let mut dependents_out = HashMap::new();
+    let mut sdeps_out = HashMap::new();
+    aux::<P>(prf.top_level_proof(), &mut deps_out, &mut dependents_out, &mut sdeps_out);
+    (deps_out, dependents_out, sdeps_out)
+}
+
+/// Second pass over the same justification-line traversal as `calculate_dependency_indices`,
+/// formatting a human-readable inlay hint for each one: the rule's display name, its
+/// dependencies rendered as resolved line numbers/ranges via `line_depth`, and a validity
+/// marker from re-running the rule's own `check`.
+fn calculate_annotations<P: Proof>(
+    prf: &P,
+    line_depth: &HashMap<PJRef<P>, (usize, usize)>,
+) -> HashMap<PJRef<P>, String> {
+    fn aux<P: Proof>(
+        p: &<P as Proof>::Subproof,
+        top: &P,
+        line_depth: &HashMap<PJRef<P>, (usize, usize)>,
+        out: &mut HashMap<PJRef<P>, String>,
+    ) {
+        use frunk::Coproduct::{Inl, Inr};
+        for lineref in p.lines() {
+            match lineref {
+                Inl(jr) => {
+                    if let Some((conclusion, rule, deps, sdeps)) = p.lookup_step(&jr) {
+                        let dep_lines = format_line_ranges::<P>(&deps, line_depth);
+                        let marker = match rule.check(top, conclusion, deps, sdeps, LogicMode::default()) {
+                            Ok(()) => '\u{2713}', // checkmark
+                            Err(_) => '\u{2717}', // ballot x
+                        };
+                        let annotation = if dep_lines.is_empty() {
+                            format!("{} {}", marker, rule.get_name())
+                        } else {
+                            format!("{} {} [{}]", marker, rule.get_name(), dep_lines)
+                        };
+                        out.insert(Coproduct::inject(jr), annotation);
+                    }
+                }
+                Inr(Inl(sr)) => aux::<P>(&p.lookup_subproof(&sr).unwrap(), top, line_depth, out),
+                Inr(Inr(void)) => match void {},
+            }
+        }
+    }
+    let mut out = HashMap::new();
+    aux::<P>(prf.top_level_proof(), prf, line_depth, &mut out);
+    out
+}
+
+/// Collapse a list of refs' resolved line numbers into a compact string like "1, 3-4" rather
+/// than listing every opaque ref.
+fn format_line_ranges<P: Proof>(
+    refs: &[PJRef<P>],
+    line_depth: &HashMap<PJRef<P>, (usize, usize)>,
+) -> String {
+    let mut lines: Vec<usize> = refs
+        .iter()
+        .filter_map(|r| line_depth.get(r).map(|(l, _)| *l))
+        .collect();
+    lines.sort_unstable();
+    lines.dedup();
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for l in lines {
+        match ranges.last_mut() {
+            Some(last) if last.1 + 1 == l => last.1 = l,
+            _ => ranges.push((l, l)),
+        }
+    }
+    ranges
+        .into_iter()
+        .map(|(a, b)| if a == b { a.to_string() } else { format!("{}-{}", a, b) })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
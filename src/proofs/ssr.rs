@@ -0,0 +1,182 @@
+//! Structural search-and-replace over proof expressions.
+//!
+//! This is the proof-editor counterpart of an IDE's structural search/replace assist: a
+//! pattern `Expr` containing `?`-prefixed metavariables (e.g. `and(?A, ?B)`) is matched
+//! structurally against the expression of every premise and justification line in the proof,
+//! and on a match the captured bindings are substituted into a replacement template and
+//! written back as the new input string for that line.
+
+use crate::proofs::proof_ui_data::initialize_inputs;
+use crate::proofs::proof_ui_data::ProofUiData;
+use crate::Expr;
+use crate::PJRef;
+use crate::Proof;
+
+use std::collections::HashMap;
+
+/// Bindings captured for a successful match: metavariable name (without the leading `?`) to
+/// the subexpression it matched.
+pub type Bindings = HashMap<String, Expr>;
+
+/// True if `name` is a pattern metavariable (`?`-prefixed).
+fn is_metavar(name: &str) -> bool {
+    name.starts_with('?')
+}
+
+/// Attempt to unify `pattern` against `expr`, extending `bindings` in place. A metavariable
+/// that's already bound (because it occurred earlier in the pattern) must match the same
+/// subterm every subsequent time it occurs.
+pub fn unify_pattern(pattern: &Expr, expr: &Expr, bindings: &mut Bindings) -> bool {
+    if let Expr::Var { name } = pattern {
+        if is_metavar(name) {
+            let key = name.trim_start_matches('?').to_string();
+            return match bindings.get(&key) {
+                Some(bound) => bound == expr,
+                None => {
+                    bindings.insert(key, expr.clone());
+                    true
+                }
+            };
+        }
+    }
+    match (pattern, expr) {
+        (Expr::Var { name: p }, Expr::Var { name: e }) => p == e,
+        (Expr::Not { operand: p }, Expr::Not { operand: e }) => unify_pattern(p, e, bindings),
+        (Expr::Impl { left: pl, right: pr }, Expr::Impl { left: el, right: er }) => {
+            unify_pattern(pl, el, bindings) && unify_pattern(pr, er, bindings)
+        }
+        (Expr::Assoc { op: po, exprs: pe }, Expr::Assoc { op: eo, exprs: ee }) => {
+            po == eo && unify_assoc(pe, ee, bindings)
+        }
+        (
+            Expr::Quant { kind: pk, name: pn, body: pb },
+            Expr::Quant { kind: ek, name: en, body: eb },
+        ) => pk == ek && pn == en && unify_pattern(pb, eb, bindings),
+        (Expr::Contra, Expr::Contra) => true,
+        _ => false,
+    }
+}
+
+/// Match an n-ary associative/commutative operator's operands against the pattern's, trying
+/// every permutation of `exprs` so that e.g. `and(?A, ?B)` matches `q & p` by binding `?A` to
+/// `q`. The search has no arity cap; its cost grows with the factorial of the arity.
+fn unify_assoc(pattern: &[Expr], exprs: &[Expr], bindings: &mut Bindings) -> bool {
+    if pattern.len() != exprs.len() {
+        return false;
+    }
+    fn permute(pattern: &[Expr], remaining: &mut Vec<Expr>, bindings: &Bindings) -> Option<Bindings> {
+        if pattern.is_empty() {
+            return Some(bindings.clone());
+        }
+        for i in 0..remaining.len() {
+            let candidate = remaining.remove(i);
+            let mut trial = bindings.clone();
+            if unify_pattern(&pattern[0], &candidate, &mut trial) {
+                if let Some(result) = permute(&pattern[1..], remaining, &trial) {
+                    remaining.insert(i, candidate);
+                    return Some(result);
+                }
+            }
+            remaining.insert(i, candidate);
+        }
+        None
+    }
+    let mut remaining = exprs.to_vec();
+    match permute(pattern, &mut remaining, bindings) {
+        Some(result) => {
+            *bindings = result;
+            true
+        }
+        None => false,
+    }
+}
+
+/// Search every premise and justification line reachable via `initialize_inputs` for a
+/// structural match of `pattern`, returning the matched ref alongside its captured bindings.
+pub fn search<P: Proof>(prf: &P, pattern: &Expr) -> Vec<(PJRef<P>, Bindings)> {
+    let inputs = initialize_inputs::<P>(prf);
+    let mut out = Vec::new();
+    for r in inputs.keys() {
+        if let Some(expr) = prf.lookup_expr(r) {
+            let mut bindings = Bindings::new();
+            if unify_pattern(pattern, &expr, &mut bindings) {
+                out.push((r.clone(), bindings));
+            }
+        }
+    }
+    out
+}
+
+/// Substitute `bindings` into `template`, replacing each `?name` metavariable with its bound
+/// subexpression (left as the literal metavariable if unbound).
+pub fn instantiate(template: &Expr, bindings: &Bindings) -> Expr {
+    match template {
+        Expr::Var { name } if is_metavar(name) => {
+            let key = name.trim_start_matches('?');
+            bindings.get(key).cloned().unwrap_or_else(|| template.clone())
+        }
+        Expr::Var { .. } | Expr::Contra => template.clone(),
+        Expr::Not { operand } => Expr::Not { operand: Box::new(instantiate(operand, bindings)) },
+        Expr::Impl { left, right } => Expr::Impl {
+            left: Box::new(instantiate(left, bindings)),
+            right: Box::new(instantiate(right, bindings)),
+        },
+        Expr::Assoc { op, exprs } => Expr::Assoc {
+            op: *op,
+            exprs: exprs.iter().map(|e| instantiate(e, bindings)).collect(),
+        },
+        Expr::Quant { kind, name, body } => Expr::Quant {
+            kind: *kind,
+            name: name.clone(),
+            body: Box::new(instantiate(body, bindings)),
+        },
+    }
+}
+
+/// True if instantiating `template` with `bindings` would put a free variable of a captured
+/// subexpression under a quantifier of the template that binds that name. The pattern is not
+/// consulted: a binder that also enclosed the metavariable in the pattern counts as capture too.
+fn would_capture(template: &Expr, bindings: &Bindings) -> bool {
+    fn aux(e: &Expr, bound: &[String], bindings: &Bindings) -> bool {
+        match e {
+            Expr::Var { name } if is_metavar(name) => {
+                let key = name.trim_start_matches('?');
+                match bindings.get(key) {
+                    Some(sub) => {
+                        let free = crate::expr::free_vars(sub);
+                        bound.iter().any(|b| free.contains(b))
+                    }
+                    None => false,
+                }
+            }
+            Expr::Quant { name, body, .. } => {
+                let mut bound = bound.to_vec();
+                bound.push(name.clone());
+                aux(body, &bound, bindings)
+            }
+            Expr::Not { operand } => aux(operand, bound, bindings),
+            Expr::Impl { left, right } => aux(left, bound, bindings) || aux(right, bound, bindings),
+            Expr::Assoc { exprs, .. } => exprs.iter().any(|e| aux(e, bound, bindings)),
+            Expr::Var { .. } | Expr::Contra => false,
+        }
+    }
+    aux(template, &[], bindings)
+}
+
+/// Find every match of `pattern` in `prf` and rewrite it to `template`, writing the new input
+/// string back through `ui.ref_to_input`. Rewrites that `would_capture` flags (any template
+/// binder over a metavariable whose binding has that name free) are silently skipped (use
+/// `search` directly to inspect why).
+/// Returns the number of lines rewritten.
+pub fn replace<P: Proof>(prf: &P, ui: &mut ProofUiData<P>, pattern: &Expr, template: &Expr) -> usize {
+    let mut count = 0;
+    for (r, bindings) in search::<P>(prf, pattern) {
+        if would_capture(template, &bindings) {
+            continue;
+        }
+        let rewritten = instantiate(template, &bindings);
+        ui.ref_to_input.insert(r, format!("{}", rewritten));
+        count += 1;
+    }
+    count
+}
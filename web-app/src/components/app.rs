@@ -23,6 +23,35 @@ pub enum AppMsg {
     CreateTab { name: String, content: Html },
     RegisterProofName { name: String, link: ComponentLink<ProofWidget> },
     GetProofFromCurrentTab(Box<dyn FnOnce(String, &P)>),
+    /// Serialize the current tab's proof the same way a `.bram` save does, then stash it
+    /// (compressed and URL-safe-encoded) in the page's URL fragment, so the tab can be reopened
+    /// from that URL alone.
+    ExportPermalink,
+    /// The inverse: read a permalink out of the current page's URL fragment (if any) and open it
+    /// as a new tab, or a tab showing the decode error if the fragment isn't one of ours.
+    ImportPermalink,
+}
+
+/// Compress `bytes` and encode the result with the URL-safe base64 alphabet (no `=` padding), so
+/// the result can go straight into a URL fragment without escaping.
+fn encode_permalink(bytes: &[u8]) -> String {
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(bytes).expect("writing to an in-memory Vec can't fail");
+    let compressed = encoder.finish().expect("writing to an in-memory Vec can't fail");
+    base64::encode_config(&compressed, base64::URL_SAFE_NO_PAD)
+}
+
+/// The inverse of `encode_permalink`.
+fn decode_permalink(encoded: &str) -> Result<Vec<u8>, String> {
+    use flate2::read::GzDecoder;
+    use std::io::Read;
+    let compressed = base64::decode_config(encoded, base64::URL_SAFE_NO_PAD).map_err(|e| format!("not a valid permalink (bad base64): {}", e))?;
+    let mut bytes = Vec::new();
+    GzDecoder::new(&compressed[..]).read_to_end(&mut bytes).map_err(|e| format!("not a valid permalink (bad compressed data): {}", e))?;
+    Ok(bytes)
 }
 
 impl Component for App {
@@ -71,6 +100,34 @@ impl Component for App {
                 }
                 false
             }
+            AppMsg::ExportPermalink => {
+                self.update(AppMsg::GetProofFromCurrentTab(Box::new(|_name, prf| {
+                    let xml = aris::proofs::xml_interop::proof_to_xml(prf);
+                    let fragment = encode_permalink(&xml);
+                    if let Err(e) = yew::utils::window().location().set_hash(&fragment) {
+                        yew::services::ConsoleService::new().error(&format!("couldn't set location.hash: {:?}", e));
+                    }
+                })))
+            }
+            AppMsg::ImportPermalink => {
+                let hash = yew::utils::window().location().hash().unwrap_or_default();
+                let fragment = hash.trim_start_matches('#').to_string();
+                if fragment.is_empty() {
+                    return false;
+                }
+                let name: String = "Imported proof".into();
+                let name_ = name.clone();
+                let content = match decode_permalink(&fragment) {
+                    Ok(xml) => match aris::proofs::xml_interop::proof_from_xml(&xml[..]) {
+                        Ok(_) => html! {
+                            <ProofWidget verbose=true data=Some(xml) oncreate=self.link.callback(move |link| AppMsg::RegisterProofName { name: name_.clone(), link }) />
+                        },
+                        Err(e) => html! { <div>{ format!("Couldn't import permalink: {}", e) }</div> },
+                    },
+                    Err(e) => html! { <div>{ format!("Couldn't import permalink: {}", e) }</div> },
+                };
+                self.update(AppMsg::CreateTab { name, content })
+            }
         }
     }
 
@@ -78,6 +135,17 @@ impl Component for App {
         false
     }
 
+    fn rendered(&mut self, first_render: bool) {
+        if first_render {
+            // By now `TabbedContainerInit`/`MenuWidgetInit` have already registered their links
+            // (both fire as `oncreate` callbacks during this same initial render), so if the
+            // page was opened with a permalink fragment, importing it here can actually land in
+            // a tab instead of being dropped for want of `tabcontainer_link`. `ImportPermalink`
+            // itself is a no-op when there's no fragment, so it's always safe to send.
+            self.link.send_message(AppMsg::ImportPermalink);
+        }
+    }
+
     fn view(&self) -> Html {
         let resolution_fname: String = "resolution_example.bram".into();
         let resolution_fname_ = resolution_fname.clone();